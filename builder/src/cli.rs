@@ -0,0 +1,68 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{FieldsNamed, Ident};
+
+use crate::{is_vector, parse_field_attrs, FieldAttrs};
+
+/// `XBuilder::from_args` for `#[builder(from_args)]`, which fills the builder
+/// from command line style arguments: `--field value` calls the setter of
+/// `field` and every `--each value` pushes one element to a vector field.
+/// Values are parsed with `FromStr`. Underscores in field names are spelled as
+/// dashes on the command line.
+pub fn build_from_args_impl(
+    fields: &FieldsNamed,
+    builder_name: &Ident,
+    struct_name: &Ident,
+) -> TokenStream {
+    let arms = fields.named.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let name = match parse_field_attrs(field) {
+            Ok(FieldAttrs {
+                each: Some(each), ..
+            }) => each,
+            _ => ident.unraw().to_string(),
+        };
+        let flag = format!("--{}", name.replace('_', "-"));
+        let missing = format!("missing value for `{}`", flag);
+        let invalid = format!("invalid value for `{}`: {{}}", flag);
+        let parse = quote! {
+            match args.next() {
+                std::option::Option::Some(value) => value.parse().map_err(|err| std::format!(#invalid, err))?,
+                std::option::Option::None => return std::result::Result::Err(#missing.into()),
+            }
+        };
+        if is_vector(&field.ty) {
+            quote! {
+                #flag => {
+                    builder.#ident.push(#parse);
+                }
+            }
+        } else {
+            quote! {
+                #flag => {
+                    builder.#ident = std::option::Option::Some(#parse);
+                }
+            }
+        }
+    });
+
+    quote! {
+        impl #builder_name {
+            pub fn from_args<I>(args: I) -> std::result::Result<Self, std::boxed::Box<dyn std::error::Error>>
+            where
+                I: std::iter::IntoIterator<Item = std::string::String>,
+            {
+                let mut builder = #struct_name::builder();
+                let mut args = args.into_iter();
+                while let std::option::Option::Some(arg) = args.next() {
+                    match arg.as_str() {
+                        #(#arms)*
+                        _ => return std::result::Result::Err(std::format!("unknown flag `{}`", arg).into()),
+                    }
+                }
+                std::result::Result::Ok(builder)
+            }
+        }
+    }
+}
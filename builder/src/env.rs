@@ -0,0 +1,78 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{FieldsNamed, Ident};
+
+use crate::{is_vector, parse_field_attrs, FieldAttrs};
+
+/// `XBuilder::from_env` for `#[builder(from_env)]`, which fills every field
+/// that is still unset from the environment variable `PREFIX_FIELD`, or the
+/// one named by `#[builder(env = "...")]`. Values are parsed with `FromStr`;
+/// vector fields are split on `#[builder(separator = "...")]`, a comma by
+/// default.
+pub fn build_from_env_impl(fields: &FieldsNamed, builder_name: &Ident) -> TokenStream {
+    let fills = fields.named.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let FieldAttrs { env, separator, .. } = parse_field_attrs(field).unwrap_or_default();
+        let var_name = match env {
+            Some(env) => quote!(std::string::String::from(#env)),
+            None => {
+                let suffix = ident.unraw().to_string().to_uppercase();
+                let prefixed = format!("{{}}_{}", suffix);
+                quote! {
+                    if prefix.is_empty() {
+                        std::string::String::from(#suffix)
+                    } else {
+                        std::format!(#prefixed, prefix)
+                    }
+                }
+            }
+        };
+        let invalid = format!("invalid value for field '{}' from `{{}}`: {{}}", ident);
+        let parse = quote! {
+            item.parse().map_err(|err| std::format!(#invalid, name, err))?
+        };
+        let (is_unset, fill) = if is_vector(&field.ty) {
+            let separator = separator.unwrap_or_else(|| ",".to_owned());
+            (
+                quote!(self.#ident.is_empty()),
+                quote! {
+                    for item in value.split(#separator).filter(|item| !item.is_empty()) {
+                        self.#ident.push(#parse);
+                    }
+                },
+            )
+        } else {
+            (
+                quote!(self.#ident.is_none()),
+                quote! {
+                    let item = value.as_str();
+                    self.#ident = std::option::Option::Some(#parse);
+                },
+            )
+        };
+        quote! {
+            if #is_unset {
+                let name = #var_name;
+                match std::env::var(&name) {
+                    std::result::Result::Ok(value) => {
+                        #fill
+                    }
+                    std::result::Result::Err(std::env::VarError::NotPresent) => {}
+                    std::result::Result::Err(err) => {
+                        return std::result::Result::Err(std::format!(#invalid, name, err).into());
+                    }
+                }
+            }
+        }
+    });
+
+    quote! {
+        impl #builder_name {
+            pub fn from_env(&mut self, prefix: &str) -> std::result::Result<&mut Self, std::boxed::Box<dyn std::error::Error>> {
+                #(#fills)*
+                std::result::Result::Ok(self)
+            }
+        }
+    }
+}
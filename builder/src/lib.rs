@@ -1,19 +1,37 @@
 use proc_macro2::{Span, TokenStream};
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
 use syn::{
-    parse_macro_input, Data, DeriveInput, Fields, FieldsNamed, GenericArgument, Ident, Lit, Meta,
-    MetaNameValue, NestedMeta, PathArguments, PathSegment, Type, Visibility,
+    parse_macro_input, Attribute, Data, DeriveInput, Field, Fields, FieldsNamed, GenericArgument,
+    Ident, Lit, Meta, MetaNameValue, NestedMeta, Path, PathArguments, PathSegment, Type,
+    Visibility,
 };
 
-enum LitOrError {
-    Lit(String),
-    Error(syn::Error),
+#[cfg(feature = "cli")]
+mod cli;
+mod env;
+
+/// Options given to the builder as a whole through `#[builder(...)]` on the
+/// struct.
+#[derive(Default)]
+struct StructAttrs {
+    /// `#[builder(const)]`: owned builder whose methods are all `const fn`.
+    is_const: bool,
+    /// `#[builder(from_args)]`: generate `from_args`, which needs the `cli`
+    /// feature and every field to implement `FromStr`.
+    from_args: bool,
+    /// `#[builder(from_env)]`: generate `from_env`, which needs every field to
+    /// implement `FromStr`.
+    from_env: bool,
 }
 
 #[proc_macro_derive(Builder, attributes(builder))]
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
+    proc_macro::TokenStream::from(expand(input))
+}
 
+fn expand(input: DeriveInput) -> TokenStream {
     let ident = input.ident;
     let vis = input.vis;
     let builder_name = format_ident!("{}Builder", ident);
@@ -21,29 +39,134 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let fields = match input.data {
         Data::Struct(data) => match data.fields {
             Fields::Named(fields) => fields,
-            _ => {
-                return syn::Error::new(ident.span(), "expects named fields")
-                    .to_compile_error()
-                    .into()
-            }
+            _ => return syn::Error::new(ident.span(), "expects named fields").to_compile_error(),
         },
-        _ => {
-            return syn::Error::new(ident.span(), "expects struct")
-                .to_compile_error()
-                .into()
-        }
+        _ => return syn::Error::new(ident.span(), "expects struct").to_compile_error(),
     };
 
-    let builder_struct = build_builder_struct(&fields, &builder_name, &vis);
-    let builder_impl = build_builder_impl(&fields, &builder_name, &ident);
-    let struct_impl = build_struct_impl(&fields, &builder_name, &ident);
-
-    let expand = quote! {
-        #builder_struct
-        #builder_impl
-        #struct_impl
+    let struct_attrs = match parse_struct_attrs(&input.attrs) {
+        Ok(attrs) => attrs,
+        Err(err) => return err.to_compile_error(),
     };
-    proc_macro::TokenStream::from(expand)
+
+    if struct_attrs.is_const {
+        if let Err(err) = check_const_fields(&fields) {
+            return err.to_compile_error();
+        }
+        let copy_assertions = build_copy_assertions(&fields);
+        let builder_struct = build_builder_struct(&fields, &builder_name, &vis);
+        let builder_impl = build_const_builder_impl(&fields, &builder_name, &ident);
+        let struct_impl = build_struct_impl(&fields, &builder_name, &ident, true);
+        quote! {
+            #copy_assertions
+            #builder_struct
+            #builder_impl
+            #struct_impl
+        }
+    } else {
+        let builder_struct = build_builder_struct(&fields, &builder_name, &vis);
+        let builder_impl = build_builder_impl(&fields, &builder_name, &ident);
+        let struct_impl = build_struct_impl(&fields, &builder_name, &ident, false);
+        #[cfg(feature = "cli")]
+        let from_args_impl = if struct_attrs.from_args {
+            cli::build_from_args_impl(&fields, &builder_name, &ident)
+        } else {
+            quote!()
+        };
+        #[cfg(not(feature = "cli"))]
+        let from_args_impl = quote!();
+        let from_env_impl = if struct_attrs.from_env {
+            env::build_from_env_impl(&fields, &builder_name)
+        } else {
+            quote!()
+        };
+        quote! {
+            #builder_struct
+            #builder_impl
+            #struct_impl
+            #from_args_impl
+            #from_env_impl
+        }
+    }
+}
+
+const STRUCT_ATTRS_EXPECTED: &str =
+    "expected `builder(const)`, `builder(from_args)` or `builder(from_env)`";
+
+fn parse_struct_attrs(attrs: &[Attribute]) -> syn::Result<StructAttrs> {
+    let mut struct_attrs = StructAttrs::default();
+    // Where a method that a const builder cannot have was asked for.
+    let mut runtime_only = None;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("builder")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(syn::Error::new_spanned(meta, STRUCT_ATTRS_EXPECTED)),
+        };
+        for nested in &list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("const") => {
+                    struct_attrs.is_const = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("from_args") => {
+                    if !cfg!(feature = "cli") {
+                        return Err(syn::Error::new_spanned(
+                            path,
+                            "`builder(from_args)` requires the `cli` feature of derive_builder",
+                        ));
+                    }
+                    struct_attrs.from_args = true;
+                    runtime_only = Some(path.clone());
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("from_env") => {
+                    struct_attrs.from_env = true;
+                    runtime_only = Some(path.clone());
+                }
+                _ => return Err(syn::Error::new_spanned(nested, STRUCT_ATTRS_EXPECTED)),
+            }
+        }
+    }
+    if let (true, Some(path)) = (struct_attrs.is_const, runtime_only) {
+        return Err(syn::Error::new_spanned(
+            &path,
+            format!(
+                "`builder({})` cannot be combined with `builder(const)`",
+                path.get_ident().unwrap()
+            ),
+        ));
+    }
+    Ok(struct_attrs)
+}
+
+/// A const builder moves its fields in and out by value, which is only
+/// possible in a `const fn` for fields that never need to be dropped.
+fn check_const_fields(fields: &FieldsNamed) -> syn::Result<()> {
+    for field in &fields.named {
+        if is_vector(&field.ty) {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                "vector fields are not supported by `builder(const)`",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Whether the other field types can be dropped in a `const fn` is only known
+/// to the compiler, so it is asked through a `Copy` bound on each of them, which
+/// reports a field that is not at its type rather than at the derive.
+fn build_copy_assertions(fields: &FieldsNamed) -> TokenStream {
+    let assertions = fields.named.iter().map(|field| {
+        let ty = &field.ty;
+        quote_spanned! {ty.span()=>
+            assert_copy::<#ty>();
+        }
+    });
+    quote! {
+        const _: fn() = || {
+            fn assert_copy<T: ::core::marker::Copy>() {}
+            #(#assertions)*
+        };
+    }
 }
 
 fn build_builder_struct(
@@ -60,7 +183,7 @@ fn build_builder_struct(
             (ident.unwrap(), ty)
         })
         .map(|(ident, ty)| {
-            if is_vector(&ty) {
+            if is_vector(ty) {
                 quote! {
                     #ident: #ty
                 }
@@ -89,64 +212,28 @@ fn build_builder_impl(
         .filter(|field| !is_vector(&field.ty))
         .map(|field| {
             let ident = field.ident.as_ref();
-            let err = format!("Required field '{}' is missing", ident.unwrap().to_string());
+            let err = format!("Required field '{}' is missing", ident.unwrap());
             quote! {
                 if self.#ident.is_none() {
-                    return Err(#err.into());
+                    return std::result::Result::Err(#err.into());
                 }
             }
         });
 
-    let setters = fields.named.iter().map(|field| {
-        let ident_each_name = field
-            .attrs
-            .first()
-            .map(|attr| match attr.parse_meta() {
-                Ok(Meta::List(list)) => {
-                    let path = &list.path;
-                    let nested = &list.nested;
-                    if !path.is_ident("builder") {
-                        return Some(LitOrError::Error(syn::Error::new(
-                            path.segments.first().unwrap().ident.span(),
-                            "expected `builder(each = \"...\")`",
-                        )));
-                    };
-                    match nested.first() {
-                        Some(NestedMeta::Meta(Meta::NameValue(MetaNameValue {
-                            ref path,
-                            eq_token: _,
-                            lit: Lit::Str(ref str),
-                        }))) => {
-                            if let Some(name) = path.segments.first() {
-                                if name.ident.to_string() != "each" {
-                                    return Some(LitOrError::Error(syn::Error::new_spanned(
-                                        list,
-                                        "expected `builder(each = \"...\")`",
-                                    )));
-                                }
-                            }
-                            if !is_vector(&field.ty) {
-                                return Some(LitOrError::Error(syn::Error::new(
-                                    field.ident.clone().unwrap().span(),
-                                    "'each' attribute can be applied to vector only",
-                                )));
-                            }
-                            Some(LitOrError::Lit(str.value()))
-                        }
-                        _ => None,
-                    }
-                }
-                _ => None,
-            })
-            .flatten();
+    let constraints = fields
+        .named
+        .iter()
+        .filter(|field| is_vector(&field.ty))
+        .map(|field| build_constraint_checks(field, &parse_field_attrs(field).unwrap_or_default()));
 
+    let setters = fields.named.iter().map(|field| {
         let ident = field.ident.as_ref();
         let ty = unwrap_option(&field.ty).unwrap_or(&field.ty);
-        match ident_each_name {
-            Some(LitOrError::Lit(name)) => {
+        match parse_field_attrs(field).map(|attrs| attrs.each) {
+            Ok(Some(name)) => {
                 let ty_each = unwrap_vector(ty).unwrap();
                 let ident_each = Ident::new(name.as_str(), Span::call_site());
-                if ident.unwrap().to_string() == name {
+                if *ident.unwrap() == name {
                     quote! {
                         pub fn #ident_each(&mut self, #ident_each:#ty_each) -> &mut Self {
                             self.#ident.push(#ident_each);
@@ -166,9 +253,9 @@ fn build_builder_impl(
                     }
                 }
             }
-            Some(LitOrError::Error(err)) => err.to_compile_error().into(),
-            None => {
-                if is_vector(&ty) {
+            Err(err) => err.to_compile_error(),
+            Ok(None) => {
+                if is_vector(ty) {
                     quote! {
                         pub fn #ident(&mut self, #ident: #ty) -> &mut Self {
                             self.#ident = #ident;
@@ -207,7 +294,8 @@ fn build_builder_impl(
 
             pub fn build(&mut self) -> std::result::Result<#struct_name, std::boxed::Box<dyn std::error::Error>> {
                 #(#checks)*
-                Ok(#struct_name {
+                #(#constraints)*
+                std::result::Result::Ok(#struct_name {
                     #(#struct_fields),*
                 })
             }
@@ -215,15 +303,117 @@ fn build_builder_impl(
     }
 }
 
+/// Checks of the `min`, `max` and `unique` constraints on a vector field.
+fn build_constraint_checks(field: &Field, attrs: &FieldAttrs) -> TokenStream {
+    let ident = field.ident.as_ref().unwrap();
+    let min = attrs.min.map(|min| {
+        let err = format!(
+            "field '{}' has {{}} elements, at least {} required",
+            ident, min
+        );
+        quote! {
+            if self.#ident.len() < #min {
+                return std::result::Result::Err(std::format!(#err, self.#ident.len()).into());
+            }
+        }
+    });
+    let max = attrs.max.map(|max| {
+        let err = format!(
+            "field '{}' has {{}} elements, at most {} allowed",
+            ident, max
+        );
+        quote! {
+            if self.#ident.len() > #max {
+                return std::result::Result::Err(std::format!(#err, self.#ident.len()).into());
+            }
+        }
+    });
+    let unique = if attrs.unique {
+        let err = format!("field '{}' has {{}} duplicate elements out of {{}}", ident);
+        quote! {
+            let duplicates = self
+                .#ident
+                .iter()
+                .enumerate()
+                .filter(|(i, item)| self.#ident[..*i].contains(item))
+                .count();
+            if duplicates > 0 {
+                return std::result::Result::Err(std::format!(#err, duplicates, self.#ident.len()).into());
+            }
+        }
+    } else {
+        quote!()
+    };
+    quote! {
+        #min
+        #max
+        #unique
+    }
+}
+
+/// Setters and `build` of a `#[builder(const)]` builder take the builder by
+/// value so that they can be `const fn`. A missing required field cannot be
+/// reported through `Result` in a `static` initializer, so `build` panics
+/// instead, which turns into a compile error during const evaluation.
+fn build_const_builder_impl(
+    fields: &FieldsNamed,
+    builder_name: &Ident,
+    struct_name: &Ident,
+) -> TokenStream {
+    let setters = fields.named.iter().map(|field| {
+        let ident = field.ident.as_ref();
+        let ty = unwrap_option(&field.ty).unwrap_or(&field.ty);
+        if let Err(err) = parse_field_attrs(field) {
+            return err.to_compile_error();
+        }
+        quote! {
+            pub const fn #ident(mut self, #ident: #ty) -> Self {
+                self.#ident = std::option::Option::Some(#ident);
+                self
+            }
+        }
+    });
+
+    let struct_fields = fields.named.iter().map(|field| {
+        let ident = field.ident.as_ref();
+        if is_option(&field.ty) {
+            quote! {
+                #ident: self.#ident
+            }
+        } else {
+            let err = format!("Required field '{}' is missing", ident.unwrap());
+            quote! {
+                #ident: match self.#ident {
+                    std::option::Option::Some(value) => value,
+                    std::option::Option::None => std::panic!(#err),
+                }
+            }
+        }
+    });
+
+    quote! {
+        impl #builder_name {
+            #(#setters)*
+
+            pub const fn build(self) -> #struct_name {
+                #struct_name {
+                    #(#struct_fields),*
+                }
+            }
+        }
+    }
+}
+
 fn build_struct_impl(
     fields: &FieldsNamed,
     builder_name: &Ident,
     struct_name: &Ident,
+    is_const: bool,
 ) -> TokenStream {
     let field_defaults = fields.named.iter().map(|field| {
         let ident = field.ident.as_ref();
         let ty = &field.ty;
-        if is_vector(&ty) {
+        if is_vector(ty) {
             quote! {
                 #ident: std::vec::Vec::new()
             }
@@ -233,9 +423,10 @@ fn build_struct_impl(
             }
         }
     });
+    let constness = if is_const { quote!(const) } else { quote!() };
     quote! {
         impl #struct_name {
-            pub fn builder() -> #builder_name {
+            pub #constness fn builder() -> #builder_name {
                 #builder_name {
                     #(#field_defaults),*
                 }
@@ -244,6 +435,123 @@ fn build_struct_impl(
     }
 }
 
+/// Options given to a single field through `#[builder(...)]`.
+#[derive(Default)]
+struct FieldAttrs {
+    /// `each = "..."`: name of the one-at-a-time setter of a vector field.
+    each: Option<String>,
+    /// `env = "..."`: environment variable read by `from_env` instead of
+    /// `PREFIX_FIELD`.
+    env: Option<String>,
+    /// `separator = "..."`: what `from_env` splits a vector field on.
+    separator: Option<String>,
+    /// `min = N`: fewest elements `build` accepts for a vector field.
+    min: Option<usize>,
+    /// `max = N`: most elements `build` accepts for a vector field.
+    max: Option<usize>,
+    /// `unique`: `build` rejects a vector field holding equal elements.
+    unique: bool,
+}
+
+fn parse_field_attrs(field: &Field) -> syn::Result<FieldAttrs> {
+    let mut field_attrs = FieldAttrs::default();
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("builder"))
+    {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "expected `builder(each = \"...\")`",
+                ))
+            }
+        };
+        for nested in &list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(str),
+                    ..
+                })) if path.is_ident("each") => {
+                    if !is_vector(&field.ty) {
+                        return Err(syn::Error::new(
+                            field.ident.clone().unwrap().span(),
+                            "'each' attribute can be applied to vector only",
+                        ));
+                    }
+                    field_attrs.each = Some(str.value());
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(str),
+                    ..
+                })) if path.is_ident("env") => {
+                    field_attrs.env = Some(str.value());
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(str),
+                    ..
+                })) if path.is_ident("separator") => {
+                    check_vector_attr(field, path)?;
+                    field_attrs.separator = Some(str.value());
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Int(int),
+                    ..
+                })) if path.is_ident("min") => {
+                    check_vector_attr(field, path)?;
+                    field_attrs.min = Some(int.base10_parse()?);
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Int(int),
+                    ..
+                })) if path.is_ident("max") => {
+                    check_vector_attr(field, path)?;
+                    field_attrs.max = Some(int.base10_parse()?);
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("unique") => {
+                    check_vector_attr(field, path)?;
+                    field_attrs.unique = true;
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        &list,
+                        "expected `builder(each = \"...\")`",
+                    ))
+                }
+            }
+        }
+    }
+    if let (Some(min), Some(max)) = (field_attrs.min, field_attrs.max) {
+        if min > max {
+            return Err(syn::Error::new(
+                field.ident.clone().unwrap().span(),
+                format!("'min' ({}) is greater than 'max' ({})", min, max),
+            ));
+        }
+    }
+    Ok(field_attrs)
+}
+
+fn check_vector_attr(field: &Field, path: &Path) -> syn::Result<()> {
+    if is_vector(&field.ty) {
+        return Ok(());
+    }
+    Err(syn::Error::new_spanned(
+        path,
+        format!(
+            "'{}' attribute can be applied to vector only",
+            path.get_ident().unwrap()
+        ),
+    ))
+}
+
 fn is_option(ty: &Type) -> bool {
     match get_last_path_segment(ty) {
         Some(seg) => seg.ident == "Option",
@@ -277,7 +585,7 @@ fn unwrap_generic_type(ty: &Type) -> Option<&Type> {
         Some(seg) => match seg.arguments {
             PathArguments::AngleBracketed(ref args) => {
                 args.args.first().and_then(|arg| match arg {
-                    &GenericArgument::Type(ref ty) => Some(ty),
+                    GenericArgument::Type(ty) => Some(ty),
                     _ => None,
                 })
             }
@@ -293,3 +601,15 @@ fn get_last_path_segment(ty: &Type) -> Option<&PathSegment> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+#[test]
+fn expand_snapshots() {
+    let snapshots = snapshot::Snapshots::new("tests/expand");
+    let snapshots = if cfg!(feature = "cli") {
+        snapshots.variant("cli")
+    } else {
+        snapshots
+    };
+    snapshots.derive("Builder", |input| expand(syn::parse2(input).unwrap()));
+}
@@ -0,0 +1,35 @@
+// A builder annotated with #[builder(const)] takes itself by value and makes
+// every generated method a const fn, so that fully built values can be used to
+// initialize a static or a const.
+//
+// Only fields that can be moved around in const context are supported, which
+// in practice means Copy types and Options of Copy types.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(const)]
+pub struct Config {
+    port: u16,
+    verbose: bool,
+    timeout: Option<u32>,
+}
+
+static CONFIG: Config = Config::builder().port(80).verbose(true).build();
+
+const TABLE: [Config; 2] = [
+    Config::builder().port(8080).verbose(false).timeout(30).build(),
+    Config::builder().verbose(false).port(8443).build(),
+];
+
+fn main() {
+    assert_eq!(CONFIG.port, 80);
+    assert!(CONFIG.verbose);
+    assert!(CONFIG.timeout.is_none());
+
+    assert_eq!(TABLE[0].timeout, Some(30));
+    assert_eq!(TABLE[1].port, 8443);
+
+    let runtime = Config::builder().port(22).verbose(false).build();
+    assert_eq!(runtime.port, 22);
+}
@@ -0,0 +1,18 @@
+// When a required field is not set, the build method of a #[builder(const)]
+// builder panics. In a static or const initializer that panic happens during
+// const evaluation and is reported as a compile error.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(const)]
+pub struct Config {
+    port: u16,
+    verbose: bool,
+}
+
+static CONFIG: Config = Config::builder().port(80).build();
+
+fn main() {
+    let _ = CONFIG.port;
+}
@@ -0,0 +1,63 @@
+// With the "cli" feature enabled, a builder annotated with
+// #[builder(from_args)] can be populated from command line style arguments.
+// Every field is set through `--field value`, where underscores in the field
+// name become dashes, and every occurrence of `--each value` appends one
+// element to a repeated field. Values are parsed with FromStr, so every field
+// type has to implement it.
+//
+// Flags that do not correspond to any field, flags without a value and values
+// that fail to parse are reported through the builder's error type.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(from_args)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(each = "env")]
+    env: Vec<String>,
+    current_dir: Option<String>,
+    jobs: Option<u32>,
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+fn main() {
+    let command = CommandBuilder::from_args(args(&[
+        "--executable",
+        "cargo",
+        "--arg",
+        "build",
+        "--arg",
+        "--release",
+        "--current-dir",
+        "..",
+        "--jobs",
+        "4",
+    ]))
+    .unwrap()
+    .build()
+    .unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build", "--release"]);
+    assert!(command.env.is_empty());
+    assert_eq!(command.current_dir.as_deref(), Some(".."));
+    assert_eq!(command.jobs, Some(4));
+
+    let err = CommandBuilder::from_args(args(&["--verbose"])).err().unwrap();
+    assert_eq!(err.to_string(), "unknown flag `--verbose`");
+
+    let err = CommandBuilder::from_args(args(&["--executable"])).err().unwrap();
+    assert_eq!(err.to_string(), "missing value for `--executable`");
+
+    let err = CommandBuilder::from_args(args(&["--jobs", "many"])).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "invalid value for `--jobs`: invalid digit found in string",
+    );
+}
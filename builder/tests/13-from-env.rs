@@ -0,0 +1,55 @@
+// Services often take their settings from the environment. The from_env method
+// generated for #[builder(from_env)] fills every field that has not been set
+// yet from the environment variable PREFIX_FIELD, parsing the value with
+// FromStr. Vector fields are split on a separator, a comma unless
+// #[builder(separator = "...")] says otherwise, and #[builder(env = "...")]
+// picks a different variable name altogether.
+//
+// A value that fails to parse is reported through the builder's error type,
+// naming both the field and the variable.
+
+use derive_builder::Builder;
+use std::env;
+
+#[derive(Builder)]
+#[builder(from_env)]
+pub struct Service {
+    host: String,
+    port: u16,
+    #[builder(each = "arg", separator = " ")]
+    args: Vec<String>,
+    #[builder(each = "peer")]
+    peers: Vec<u16>,
+    #[builder(env = "SERVICE_LOG_LEVEL")]
+    log_level: Option<String>,
+    workers: Option<u32>,
+}
+
+fn main() {
+    env::set_var("APP_HOST", "example.com");
+    env::set_var("APP_PORT", "8080");
+    env::set_var("APP_ARGS", "--quiet --color never");
+    env::set_var("APP_PEERS", "8081,8082");
+    env::set_var("SERVICE_LOG_LEVEL", "debug");
+
+    let service = Service::builder()
+        .host("localhost".to_owned())
+        .from_env("APP")
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(service.host, "localhost");
+    assert_eq!(service.port, 8080);
+    assert_eq!(service.args, vec!["--quiet", "--color", "never"]);
+    assert_eq!(service.peers, vec![8081, 8082]);
+    assert_eq!(service.log_level.as_deref(), Some("debug"));
+    assert!(service.workers.is_none());
+
+    env::set_var("BAD_PORT", "http");
+    let err = Service::builder().from_env("BAD").err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "invalid value for field 'port' from `BAD_PORT`: invalid digit found in string",
+    );
+}
@@ -0,0 +1,63 @@
+// Repeated fields can carry constraints on their contents which the build
+// method enforces: #[builder(min = N)] and #[builder(max = N)] bound the number
+// of elements, and #[builder(unique)] rejects equal elements. Violations are
+// reported through the builder's error type with the field name and the
+// offending counts.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg", min = 1, max = 3)]
+    args: Vec<String>,
+    #[builder(each = "env", unique)]
+    env: Vec<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .env("RUST_LOG=debug".to_owned())
+        .env("RUST_BACKTRACE=1".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.args, vec!["build"]);
+    assert_eq!(command.env.len(), 2);
+
+    let err = Command::builder()
+        .executable("cargo".to_owned())
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "field 'args' has 0 elements, at least 1 required",
+    );
+
+    let err = Command::builder()
+        .executable("cargo".to_owned())
+        .args(vec!["a".to_owned(), "b".to_owned(), "c".to_owned(), "d".to_owned()])
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "field 'args' has 4 elements, at most 3 allowed",
+    );
+
+    let err = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .env("RUST_LOG=debug".to_owned())
+        .env("RUST_BACKTRACE=1".to_owned())
+        .env("RUST_LOG=debug".to_owned())
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "field 'env' has 1 duplicate elements out of 3",
+    );
+}
@@ -0,0 +1,20 @@
+// The fields of a #[builder(const)] builder are replaced by value in const fn
+// setters, which requires that they never need to be dropped. A field type
+// that is not Copy is reported at the field.
+//
+// Whether a type is Copy is not known to the derive, so the const fn setters
+// are generated anyway, and the compiler follows up with E0493 errors about
+// their destructors at the derive. Those are expected; the errors at the
+// fields come first and say what to change.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(const)]
+pub struct Config {
+    port: u16,
+    name: String,
+    label: Option<String>,
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+// Parsing a builder from arguments or the environment requires FromStr of
+// every field, so those methods are only generated when asked for. A builder
+// that does not ask for them works with any field type.
+
+use derive_builder::Builder;
+use std::time::Duration;
+
+#[derive(Builder)]
+pub struct Timeouts {
+    connect: Duration,
+    read: Option<Duration>,
+    #[builder(each = "retry")]
+    retries: Vec<Duration>,
+}
+
+fn main() {
+    let timeouts = Timeouts::builder()
+        .connect(Duration::from_secs(5))
+        .retry(Duration::from_millis(100))
+        .build()
+        .unwrap();
+
+    assert_eq!(timeouts.connect, Duration::from_secs(5));
+    assert!(timeouts.read.is_none());
+    assert_eq!(timeouts.retries, vec![Duration::from_millis(100)]);
+}
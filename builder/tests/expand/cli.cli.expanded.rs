@@ -0,0 +1,125 @@
+pub struct CommandBuilder {
+    executable: std::option::Option<String>,
+    args: Vec<String>,
+    jobs: std::option::Option<u32>,
+}
+impl CommandBuilder {
+    pub fn executable(&mut self, executable: String) -> &mut Self {
+        self.executable = std::option::Option::Some(executable);
+        self
+    }
+    pub fn args(&mut self, args: Vec<String>) -> &mut Self {
+        self.args = args;
+        self
+    }
+    pub fn arg(&mut self, arg: String) -> &mut Self {
+        self.args.push(arg);
+        self
+    }
+    pub fn jobs(&mut self, jobs: u32) -> &mut Self {
+        self.jobs = std::option::Option::Some(jobs);
+        self
+    }
+    pub fn build(
+        &mut self,
+    ) -> std::result::Result<Command, std::boxed::Box<dyn std::error::Error>> {
+        if self.executable.is_none() {
+            return std::result::Result::Err(
+                "Required field 'executable' is missing".into(),
+            );
+        }
+        std::result::Result::Ok(Command {
+            executable: self.executable.clone().unwrap(),
+            args: self.args.clone(),
+            jobs: self.jobs.clone(),
+        })
+    }
+}
+impl Command {
+    pub fn builder() -> CommandBuilder {
+        CommandBuilder {
+            executable: std::option::Option::None,
+            args: std::vec::Vec::new(),
+            jobs: std::option::Option::None,
+        }
+    }
+}
+impl CommandBuilder {
+    pub fn from_args<I>(
+        args: I,
+    ) -> std::result::Result<Self, std::boxed::Box<dyn std::error::Error>>
+    where
+        I: std::iter::IntoIterator<Item = std::string::String>,
+    {
+        let mut builder = Command::builder();
+        let mut args = args.into_iter();
+        while let std::option::Option::Some(arg) = args.next() {
+            match arg.as_str() {
+                "--executable" => {
+                    builder
+                        .executable = std::option::Option::Some(
+                        match args.next() {
+                            std::option::Option::Some(value) => {
+                                value
+                                    .parse()
+                                    .map_err(|err| {
+                                        std::format!("invalid value for `--executable`: {}", err)
+                                    })?
+                            }
+                            std::option::Option::None => {
+                                return std::result::Result::Err(
+                                    "missing value for `--executable`".into(),
+                                );
+                            }
+                        },
+                    );
+                }
+                "--arg" => {
+                    builder
+                        .args
+                        .push(
+                            match args.next() {
+                                std::option::Option::Some(value) => {
+                                    value
+                                        .parse()
+                                        .map_err(|err| {
+                                            std::format!("invalid value for `--arg`: {}", err)
+                                        })?
+                                }
+                                std::option::Option::None => {
+                                    return std::result::Result::Err(
+                                        "missing value for `--arg`".into(),
+                                    );
+                                }
+                            },
+                        );
+                }
+                "--jobs" => {
+                    builder
+                        .jobs = std::option::Option::Some(
+                        match args.next() {
+                            std::option::Option::Some(value) => {
+                                value
+                                    .parse()
+                                    .map_err(|err| {
+                                        std::format!("invalid value for `--jobs`: {}", err)
+                                    })?
+                            }
+                            std::option::Option::None => {
+                                return std::result::Result::Err(
+                                    "missing value for `--jobs`".into(),
+                                );
+                            }
+                        },
+                    );
+                }
+                _ => {
+                    return std::result::Result::Err(
+                        std::format!("unknown flag `{}`", arg).into(),
+                    );
+                }
+            }
+        }
+        std::result::Result::Ok(builder)
+    }
+}
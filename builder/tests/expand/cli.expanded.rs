@@ -0,0 +1,3 @@
+compile_error! {
+    "`builder(from_args)` requires the `cli` feature of derive_builder"
+}
@@ -0,0 +1,10 @@
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(from_args)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    jobs: Option<u32>,
+}
@@ -0,0 +1,214 @@
+pub struct CommandBuilder {
+    executable: std::option::Option<String>,
+    args: Vec<String>,
+    env: Vec<String>,
+    current_dir: std::option::Option<String>,
+}
+impl CommandBuilder {
+    pub fn executable(&mut self, executable: String) -> &mut Self {
+        self.executable = std::option::Option::Some(executable);
+        self
+    }
+    pub fn args(&mut self, args: Vec<String>) -> &mut Self {
+        self.args = args;
+        self
+    }
+    pub fn arg(&mut self, arg: String) -> &mut Self {
+        self.args.push(arg);
+        self
+    }
+    pub fn env(&mut self, env: String) -> &mut Self {
+        self.env.push(env);
+        self
+    }
+    pub fn current_dir(&mut self, current_dir: String) -> &mut Self {
+        self.current_dir = std::option::Option::Some(current_dir);
+        self
+    }
+    pub fn build(
+        &mut self,
+    ) -> std::result::Result<Command, std::boxed::Box<dyn std::error::Error>> {
+        if self.executable.is_none() {
+            return std::result::Result::Err(
+                "Required field 'executable' is missing".into(),
+            );
+        }
+        if self.args.len() > 8usize {
+            return std::result::Result::Err(
+                std::format!(
+                    "field 'args' has {} elements, at most 8 allowed", self.args.len()
+                )
+                    .into(),
+            );
+        }
+        let duplicates = self
+            .env
+            .iter()
+            .enumerate()
+            .filter(|(i, item)| self.env[..*i].contains(item))
+            .count();
+        if duplicates > 0 {
+            return std::result::Result::Err(
+                std::format!(
+                    "field 'env' has {} duplicate elements out of {}", duplicates, self
+                    .env.len()
+                )
+                    .into(),
+            );
+        }
+        std::result::Result::Ok(Command {
+            executable: self.executable.clone().unwrap(),
+            args: self.args.clone(),
+            env: self.env.clone(),
+            current_dir: self.current_dir.clone(),
+        })
+    }
+}
+impl Command {
+    pub fn builder() -> CommandBuilder {
+        CommandBuilder {
+            executable: std::option::Option::None,
+            args: std::vec::Vec::new(),
+            env: std::vec::Vec::new(),
+            current_dir: std::option::Option::None,
+        }
+    }
+}
+impl CommandBuilder {
+    pub fn from_env(
+        &mut self,
+        prefix: &str,
+    ) -> std::result::Result<&mut Self, std::boxed::Box<dyn std::error::Error>> {
+        if self.executable.is_none() {
+            let name = if prefix.is_empty() {
+                std::string::String::from("EXECUTABLE")
+            } else {
+                std::format!("{}_EXECUTABLE", prefix)
+            };
+            match std::env::var(&name) {
+                std::result::Result::Ok(value) => {
+                    let item = value.as_str();
+                    self
+                        .executable = std::option::Option::Some(
+                        item
+                            .parse()
+                            .map_err(|err| {
+                                std::format!(
+                                    "invalid value for field 'executable' from `{}`: {}", name,
+                                    err
+                                )
+                            })?,
+                    );
+                }
+                std::result::Result::Err(std::env::VarError::NotPresent) => {}
+                std::result::Result::Err(err) => {
+                    return std::result::Result::Err(
+                        std::format!(
+                            "invalid value for field 'executable' from `{}`: {}", name,
+                            err
+                        )
+                            .into(),
+                    );
+                }
+            }
+        }
+        if self.args.is_empty() {
+            let name = if prefix.is_empty() {
+                std::string::String::from("ARGS")
+            } else {
+                std::format!("{}_ARGS", prefix)
+            };
+            match std::env::var(&name) {
+                std::result::Result::Ok(value) => {
+                    for item in value.split(",").filter(|item| !item.is_empty()) {
+                        self.args
+                            .push(
+                                item
+                                    .parse()
+                                    .map_err(|err| {
+                                        std::format!(
+                                            "invalid value for field 'args' from `{}`: {}", name, err
+                                        )
+                                    })?,
+                            );
+                    }
+                }
+                std::result::Result::Err(std::env::VarError::NotPresent) => {}
+                std::result::Result::Err(err) => {
+                    return std::result::Result::Err(
+                        std::format!(
+                            "invalid value for field 'args' from `{}`: {}", name, err
+                        )
+                            .into(),
+                    );
+                }
+            }
+        }
+        if self.env.is_empty() {
+            let name = if prefix.is_empty() {
+                std::string::String::from("ENV")
+            } else {
+                std::format!("{}_ENV", prefix)
+            };
+            match std::env::var(&name) {
+                std::result::Result::Ok(value) => {
+                    for item in value.split(",").filter(|item| !item.is_empty()) {
+                        self.env
+                            .push(
+                                item
+                                    .parse()
+                                    .map_err(|err| {
+                                        std::format!(
+                                            "invalid value for field 'env' from `{}`: {}", name, err
+                                        )
+                                    })?,
+                            );
+                    }
+                }
+                std::result::Result::Err(std::env::VarError::NotPresent) => {}
+                std::result::Result::Err(err) => {
+                    return std::result::Result::Err(
+                        std::format!(
+                            "invalid value for field 'env' from `{}`: {}", name, err
+                        )
+                            .into(),
+                    );
+                }
+            }
+        }
+        if self.current_dir.is_none() {
+            let name = if prefix.is_empty() {
+                std::string::String::from("CURRENT_DIR")
+            } else {
+                std::format!("{}_CURRENT_DIR", prefix)
+            };
+            match std::env::var(&name) {
+                std::result::Result::Ok(value) => {
+                    let item = value.as_str();
+                    self
+                        .current_dir = std::option::Option::Some(
+                        item
+                            .parse()
+                            .map_err(|err| {
+                                std::format!(
+                                    "invalid value for field 'current_dir' from `{}`: {}", name,
+                                    err
+                                )
+                            })?,
+                    );
+                }
+                std::result::Result::Err(std::env::VarError::NotPresent) => {}
+                std::result::Result::Err(err) => {
+                    return std::result::Result::Err(
+                        std::format!(
+                            "invalid value for field 'current_dir' from `{}`: {}", name,
+                            err
+                        )
+                            .into(),
+                    );
+                }
+            }
+        }
+        std::result::Result::Ok(self)
+    }
+}
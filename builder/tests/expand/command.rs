@@ -0,0 +1,12 @@
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(from_env)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg", max = 8)]
+    args: Vec<String>,
+    #[builder(each = "env", unique)]
+    env: Vec<String>,
+    current_dir: Option<String>,
+}
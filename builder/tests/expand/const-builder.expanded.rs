@@ -0,0 +1,38 @@
+const _: fn() = || {
+    fn assert_copy<T: ::core::marker::Copy>() {}
+    assert_copy::<u16>();
+    assert_copy::<Option<u32>>();
+};
+pub struct ConfigBuilder {
+    port: std::option::Option<u16>,
+    timeout: std::option::Option<u32>,
+}
+impl ConfigBuilder {
+    pub const fn port(mut self, port: u16) -> Self {
+        self.port = std::option::Option::Some(port);
+        self
+    }
+    pub const fn timeout(mut self, timeout: u32) -> Self {
+        self.timeout = std::option::Option::Some(timeout);
+        self
+    }
+    pub const fn build(self) -> Config {
+        Config {
+            port: match self.port {
+                std::option::Option::Some(value) => value,
+                std::option::Option::None => {
+                    std::panic!("Required field 'port' is missing")
+                }
+            },
+            timeout: self.timeout,
+        }
+    }
+}
+impl Config {
+    pub const fn builder() -> ConfigBuilder {
+        ConfigBuilder {
+            port: std::option::Option::None,
+            timeout: std::option::Option::None,
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(const)]
+pub struct Config {
+    port: u16,
+    timeout: Option<u32>,
+}
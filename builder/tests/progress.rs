@@ -10,4 +10,12 @@ fn tests() {
     t.pass("tests/07-repeated-field.rs");
     t.compile_fail("tests/08-unrecognized-attribute.rs");
     t.pass("tests/09-redefined-prelude-types.rs");
+    t.pass("tests/10-const-builder.rs");
+    t.compile_fail("tests/11-const-missing-field.rs");
+    #[cfg(feature = "cli")]
+    t.pass("tests/12-from-args.rs");
+    t.pass("tests/13-from-env.rs");
+    t.pass("tests/14-collection-constraints.rs");
+    t.compile_fail("tests/15-const-non-copy-field.rs");
+    t.pass("tests/16-no-from-str-field.rs");
 }
@@ -0,0 +1,538 @@
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
+use syn::{
+    Attribute, ExprPath, Field, Fields, Lit, LitInt, LitStr, Meta, MetaNameValue, NestedMeta, Path,
+    Token, Variant, WherePredicate,
+};
+
+use crate::format::{Arg, Piece, Spec, Template};
+
+/// What `#[debug(redact)]` prints unless given a placeholder of its own.
+const REDACTED: &str = "[REDACTED]";
+
+/// Options given to the impl as a whole through `#[debug(...)]` on the type.
+#[derive(Default)]
+pub struct TypeAttrs {
+    /// `bound = "..."`: where clause used instead of the inferred bounds.
+    pub bound: Option<Punctuated<WherePredicate, Token![,]>>,
+    /// `redact` or `redact = "..."`: placeholder printed for every field.
+    pub redact: Option<String>,
+    /// `compact`: single-line output even under `{:#?}`.
+    pub compact: bool,
+    /// `non_exhaustive`: end with `..` when fields were left out.
+    pub non_exhaustive: bool,
+    /// `omit_empty`: leave out fields which are `None` or empty collections.
+    pub omit_empty: bool,
+    /// `defmt`: also implement `defmt::Format`.
+    pub defmt: bool,
+    /// `ufmt`: also implement `ufmt::uDebug`.
+    pub ufmt: bool,
+    /// `valuable`: also implement `valuable::Valuable`.
+    pub valuable: bool,
+    /// `rename_all = "..."`: case convention the field names are printed in.
+    pub rename_all: Option<RenameRule>,
+    /// `max_depth = N`: nesting of the type within itself printed before
+    /// giving up with `…`. Needs the `std` feature, as the depth is kept in a
+    /// thread-local.
+    pub max_depth: Option<usize>,
+    /// `detect_cycles`: print `<cycle>` for a value already being printed
+    /// further out, as reached again through an `Rc` or `Arc`. Needs the `std`
+    /// feature, as the values being printed are kept in a thread-local.
+    pub detect_cycles: bool,
+    /// `flattenable`: let fields of this type be marked `#[debug(flatten)]`,
+    /// through a hidden `__debug_flatten` method.
+    pub flattenable: bool,
+}
+
+/// Options given to a single enum variant.
+#[derive(Default)]
+pub struct VariantAttrs {
+    /// `#[debug = "..."]`: format string the whole variant is written with.
+    pub format: Option<LitStr>,
+}
+
+/// Options given to a single field.
+#[derive(Default)]
+pub struct FieldAttrs {
+    /// `#[debug = "..."]`: format string the field is printed with.
+    pub format: Option<LitStr>,
+    /// `#[debug(skip)]`: leave the field out altogether.
+    pub skip: bool,
+    /// `#[debug(redact)]` or `#[debug(redact = "...")]`: placeholder printed
+    /// instead of the value.
+    pub redact: Option<String>,
+    /// `#[debug(with = "...")]`: function printing the field in place of its
+    /// Debug impl.
+    pub with: Option<ExprPath>,
+    /// `#[debug(bound = "...")]`: where clause used instead of the bounds
+    /// inferred from this field's type.
+    pub bound: Option<Punctuated<WherePredicate, Token![,]>>,
+    /// `#[debug(rename = "...")]`: name the field is printed under.
+    pub rename: Option<String>,
+    /// `#[debug(flatten)]`: print the fields of the nested struct, which has to
+    /// be `#[debug(flattenable)]`, in place of the field itself.
+    pub flatten: bool,
+    /// `#[debug(truncate = N)]`: number of items, or characters of a string,
+    /// printed before the count of those left out.
+    pub truncate: Option<LitInt>,
+}
+
+/// The case conventions of `rename_all`, applied to snake_case field names.
+#[derive(Clone, Copy)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn parse(str: &LitStr) -> syn::Result<Self> {
+        Ok(match str.value().as_str() {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            _ => {
+                return Err(syn::Error::new(
+                    str.span(),
+                    "unknown case, expected one of `lowercase`, `UPPERCASE`, `PascalCase`, \
+                     `camelCase`, `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case` or \
+                     `SCREAMING-KEBAB-CASE`",
+                ))
+            }
+        })
+    }
+
+    pub fn apply(self, name: &str) -> String {
+        let words = name.split('_').filter(|word| !word.is_empty());
+        match self {
+            RenameRule::Lower => name.to_lowercase(),
+            RenameRule::Upper => name.to_uppercase(),
+            RenameRule::Pascal => words.map(capitalize).collect(),
+            RenameRule::Camel => {
+                let pascal: String = words.map(capitalize).collect();
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_lowercase().chain(chars).collect(),
+                    None => pascal,
+                }
+            }
+            RenameRule::Snake => name.to_owned(),
+            RenameRule::ScreamingSnake => name.to_uppercase(),
+            RenameRule::Kebab => name.replace('_', "-"),
+            RenameRule::ScreamingKebab => name.replace('_', "-").to_uppercase(),
+        }
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// The name a named field is printed under.
+pub fn field_name(field: &Field, field_attrs: &FieldAttrs, type_attrs: &TypeAttrs) -> String {
+    let name = field.ident.as_ref().unwrap().unraw().to_string();
+    match (&field_attrs.rename, type_attrs.rename_all) {
+        (Some(rename), _) => rename.clone(),
+        (None, Some(rule)) => rule.apply(&name),
+        (None, None) => name,
+    }
+}
+
+pub fn parse_type_attrs(attrs: &[Attribute]) -> syn::Result<TypeAttrs> {
+    let mut type_attrs = TypeAttrs::default();
+    for nested in debug_lists(attrs, "expected `debug(bound = \"...\")`")? {
+        match &nested {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(str),
+                ..
+            })) if path.is_ident("bound") => {
+                type_attrs.bound = Some(str.parse_with(Punctuated::parse_terminated)?);
+            }
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(str),
+                ..
+            })) if path.is_ident("rename_all") => {
+                type_attrs.rename_all = Some(RenameRule::parse(str)?);
+            }
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Int(int),
+                ..
+            })) if path.is_ident("max_depth") => {
+                require_std(path, "max_depth = N")?;
+                let max_depth = int.base10_parse()?;
+                if max_depth == 0 {
+                    return Err(syn::Error::new(int.span(), "`max_depth` must be at least 1"));
+                }
+                type_attrs.max_depth = Some(max_depth);
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("detect_cycles") => {
+                require_std(path, "detect_cycles")?;
+                type_attrs.detect_cycles = true;
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("flattenable") => {
+                type_attrs.flattenable = true;
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("compact") => {
+                type_attrs.compact = true;
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("non_exhaustive") => {
+                type_attrs.non_exhaustive = true;
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("omit_empty") => {
+                type_attrs.omit_empty = true;
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("defmt") => {
+                if cfg!(not(feature = "defmt")) {
+                    return Err(syn::Error::new_spanned(
+                        path,
+                        "`debug(defmt)` requires the `defmt` feature of derive_debug",
+                    ));
+                }
+                type_attrs.defmt = true;
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("ufmt") => {
+                if cfg!(not(feature = "ufmt")) {
+                    return Err(syn::Error::new_spanned(
+                        path,
+                        "`debug(ufmt)` requires the `ufmt` feature of derive_debug",
+                    ));
+                }
+                type_attrs.ufmt = true;
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("valuable") => {
+                if cfg!(not(feature = "valuable")) {
+                    return Err(syn::Error::new_spanned(
+                        path,
+                        "`debug(valuable)` requires the `valuable` feature of derive_debug",
+                    ));
+                }
+                type_attrs.valuable = true;
+            }
+            _ => match parse_redact(&nested) {
+                Some(redact) => type_attrs.redact = Some(redact),
+                None => {
+                    return Err(syn::Error::new_spanned(
+                        nested,
+                        "expected one of `bound = \"...\"`, `redact`, `compact`, `non_exhaustive`, `omit_empty`, `rename_all = \"...\"`, `max_depth = N`, `detect_cycles`, `flattenable`, `defmt`, `ufmt` or `valuable`",
+                    ))
+                }
+            },
+        }
+    }
+    Ok(type_attrs)
+}
+
+/// The recursion guards keep their state in `std::thread_local!`, which a
+/// `no_std` crate, building derive_debug without its default `std` feature,
+/// does not have.
+fn require_std(path: &Path, option: &str) -> syn::Result<()> {
+    if cfg!(feature = "std") {
+        return Ok(());
+    }
+    Err(syn::Error::new_spanned(
+        path,
+        format!(
+            "`debug({})` requires the `std` feature of derive_debug, as it keeps its \
+             state in a thread-local",
+            option
+        ),
+    ))
+}
+
+pub fn parse_variant_attrs(variant: &Variant) -> syn::Result<VariantAttrs> {
+    let mut variant_attrs = VariantAttrs::default();
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("debug"))
+    {
+        match attr.parse_meta()? {
+            Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(str), ..
+            }) => {
+                check_variant_format(&str, &variant.fields)?;
+                variant_attrs.format = Some(str);
+            }
+            meta => return Err(syn::Error::new_spanned(meta, "expected `debug = \"...\"`")),
+        }
+    }
+    Ok(variant_attrs)
+}
+
+pub fn parse_field_attrs(field: &Field) -> syn::Result<FieldAttrs> {
+    let mut field_attrs = FieldAttrs::default();
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("debug"))
+    {
+        let list = match attr.parse_meta()? {
+            Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(str), ..
+            }) => {
+                check_field_format(&str)?;
+                field_attrs.format = Some(str);
+                continue;
+            }
+            Meta::List(list) => list,
+            meta => return Err(syn::Error::new_spanned(meta, "expected `debug = \"...\"`")),
+        };
+        for nested in list.nested {
+            match &nested {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => {
+                    field_attrs.skip = true;
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(str),
+                    ..
+                })) if path.is_ident("with") => {
+                    field_attrs.with = Some(str.parse()?);
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(str),
+                    ..
+                })) if path.is_ident("bound") => {
+                    field_attrs.bound = Some(str.parse_with(Punctuated::parse_terminated)?);
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(str),
+                    ..
+                })) if path.is_ident("rename") => {
+                    if field.ident.is_none() {
+                        return Err(syn::Error::new_spanned(
+                            nested,
+                            "`debug(rename = \"...\")` requires a named field",
+                        ));
+                    }
+                    field_attrs.rename = Some(str.value());
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Int(int),
+                    ..
+                })) if path.is_ident("truncate") => {
+                    int.base10_parse::<usize>()?;
+                    field_attrs.truncate = Some(int.clone());
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("flatten") => {
+                    if field.ident.is_none() {
+                        return Err(syn::Error::new_spanned(
+                            nested,
+                            "`debug(flatten)` requires a named field",
+                        ));
+                    }
+                    field_attrs.flatten = true;
+                }
+                _ => match parse_redact(&nested) {
+                    Some(redact) => field_attrs.redact = Some(redact),
+                    None => return Err(syn::Error::new_spanned(
+                        nested,
+                        "expected one of `skip`, `redact`, `with = \"...\"`, `bound = \"...\"`, \
+                             `rename = \"...\"`, `flatten` or `truncate = N`",
+                    )),
+                },
+            }
+        }
+    }
+    if let (Some(with), Some(_)) = (&field_attrs.with, &field_attrs.format) {
+        return Err(syn::Error::new_spanned(
+            with,
+            "`debug(with = \"...\")` cannot be combined with a format string",
+        ));
+    }
+    if field_attrs.flatten
+        && (field_attrs.format.is_some()
+            || field_attrs.with.is_some()
+            || field_attrs.redact.is_some())
+    {
+        return Err(syn::Error::new(
+            field.ident.as_ref().unwrap().span(),
+            "`debug(flatten)` cannot be combined with a format string, `with` or `redact`",
+        ));
+    }
+    if let Some(truncate) = &field_attrs.truncate {
+        if field_attrs.format.is_some()
+            || field_attrs.with.is_some()
+            || field_attrs.redact.is_some()
+            || field_attrs.flatten
+        {
+            return Err(syn::Error::new(
+                truncate.span(),
+                "`debug(truncate = N)` cannot be combined with a format string, `with`, `redact` \
+                 or `flatten`",
+            ));
+        }
+    }
+    Ok(field_attrs)
+}
+
+/// The arguments a format string refers to, in order, with each `{}` resolved
+/// to the positional argument it takes. Errors are reported at the literal, as
+/// the parts of it they concern have no spans of their own.
+fn referenced_args(format: &LitStr) -> syn::Result<Vec<Arg>> {
+    let error = |message| syn::Error::new(format.span(), message);
+    let template = Template::parse(&format.value()).map_err(error)?;
+    let mut args = Vec::new();
+    let mut next = 0;
+    for piece in template.pieces {
+        if let Piece::Arg { arg, spec } = piece {
+            let spec = Spec::parse(&spec).map_err(error)?;
+            let counts = spec.counts.into_iter().map(|(count, _)| count);
+            for arg in counts.chain(Some(arg)) {
+                args.push(match arg {
+                    Arg::Next => {
+                        next += 1;
+                        Arg::Index(next - 1)
+                    }
+                    arg => arg,
+                });
+            }
+        }
+    }
+    Ok(args)
+}
+
+/// The formatting trait each placeholder of a format string asks of the
+/// argument it prints, such as `LowerHex` for `{:x}`, with each `{}` resolved
+/// to the positional argument it takes.
+pub fn placeholder_traits(format: &LitStr) -> syn::Result<Vec<(Arg, &'static str)>> {
+    let error = |message| syn::Error::new(format.span(), message);
+    let template = Template::parse(&format.value()).map_err(error)?;
+    let mut traits = Vec::new();
+    let mut next = 0;
+    for piece in template.pieces {
+        if let Piece::Arg { arg, spec } = piece {
+            let spec = Spec::parse(&spec).map_err(error)?;
+            // `{:.*}` takes its precision from the next argument first.
+            next += spec
+                .counts
+                .iter()
+                .filter(|(count, _)| matches!(count, Arg::Next))
+                .count();
+            let arg = match arg {
+                Arg::Next => {
+                    next += 1;
+                    Arg::Index(next - 1)
+                }
+                arg => arg,
+            };
+            traits.push((arg, spec.format_trait));
+        }
+    }
+    Ok(traits)
+}
+
+/// A field's format string gets the field as its one positional argument.
+fn check_field_format(format: &LitStr) -> syn::Result<()> {
+    let args = referenced_args(format)?;
+    for arg in &args {
+        let message = match arg {
+            Arg::Index(0) => continue,
+            Arg::Index(index) => format!(
+                "format string refers to argument {}, but the field is its only argument",
+                index,
+            ),
+            Arg::Named(name) => format!(
+                "format string refers to `{}`, but the field is its only argument",
+                name,
+            ),
+            Arg::Next => unreachable!(),
+        };
+        return Err(syn::Error::new(format.span(), message));
+    }
+    if args.is_empty() {
+        return Err(syn::Error::new(
+            format.span(),
+            "format string has no placeholder for the field, as in `{:?}`",
+        ));
+    }
+    Ok(())
+}
+
+/// A variant's format string refers to named fields by name and gets tuple
+/// fields as positional arguments, each of which has to be used.
+fn check_variant_format(format: &LitStr, fields: &Fields) -> syn::Result<()> {
+    let mut used = vec![false; fields.len()];
+    for arg in referenced_args(format)? {
+        let index =
+            match (&arg, fields) {
+                (Arg::Named(name), Fields::Named(_)) => fields
+                    .iter()
+                    .position(|field| field.ident.as_ref().unwrap().unraw() == name),
+                (Arg::Index(_), Fields::Named(_)) => return Err(syn::Error::new(
+                    format.span(),
+                    "placeholders of a variant with named fields must name a field, as in `{code}`",
+                )),
+                (Arg::Index(index), _) if *index < fields.len() => Some(*index),
+                _ => None,
+            };
+        match index {
+            Some(index) => used[index] = true,
+            None => {
+                let arg = match arg {
+                    Arg::Index(index) => index.to_string(),
+                    Arg::Named(name) => name,
+                    Arg::Next => unreachable!(),
+                };
+                return Err(syn::Error::new(
+                    format.span(),
+                    format!("no field `{}` in this variant", arg),
+                ));
+            }
+        }
+    }
+    if let Fields::Unnamed(_) = fields {
+        if let Some(unused) = used.iter().position(|used| !used) {
+            return Err(syn::Error::new(
+                format.span(),
+                format!("format string never uses field `{}`", unused),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The nested metas of every `#[debug(...)]` attribute.
+fn debug_lists(attrs: &[Attribute], expected: &str) -> syn::Result<Vec<NestedMeta>> {
+    let mut nested = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("debug")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => nested.extend(list.nested),
+            meta => return Err(syn::Error::new_spanned(meta, expected)),
+        }
+    }
+    Ok(nested)
+}
+
+/// `redact` or `redact = "..."`, giving the placeholder.
+fn parse_redact(nested: &NestedMeta) -> Option<String> {
+    match nested {
+        NestedMeta::Meta(Meta::Path(path)) if path.is_ident("redact") => Some(REDACTED.to_owned()),
+        NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+            path,
+            lit: Lit::Str(str),
+            ..
+        })) if path.is_ident("redact") => Some(str.value()),
+        _ => None,
+    }
+}
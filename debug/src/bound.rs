@@ -0,0 +1,121 @@
+use quote::ToTokens;
+use syn::visit::{self, Visit};
+use syn::{
+    parse_quote, Generics, Ident, Path, Type, TypeBareFn, TypePath, TypeTraitObject, WherePredicate,
+};
+
+/// Infers the bounds on `trait_path`, such as `::core::fmt::Debug`, which the
+/// generated impl needs in order to format fields of the given types.
+///
+/// A type parameter is bounded only if some field mentions it outside of a
+/// `PhantomData`, a trait object or a function pointer, all of which implement
+/// Debug whatever their parameters are. Associated types of a type parameter,
+/// whether written `T::Value` or `<T as Trait>::Value`, are bounded themselves
+/// rather than through their parameter.
+pub fn infer<'a>(
+    generics: &Generics,
+    types: impl IntoIterator<Item = &'a Type>,
+    trait_path: &Path,
+) -> Vec<WherePredicate> {
+    let params: Vec<&Ident> = generics.type_params().map(|param| &param.ident).collect();
+    let mut visitor = BoundVisitor {
+        params: &params,
+        used: Vec::new(),
+        associated: Vec::new(),
+    };
+    for ty in types {
+        visitor.visit_type(ty);
+    }
+
+    let used = params
+        .iter()
+        .filter(|param| visitor.used.contains(param))
+        .map(|param| parse_quote!(#param: #trait_path));
+    let associated = visitor
+        .associated
+        .iter()
+        .map(|path| parse_quote!(#path: #trait_path));
+    used.chain(associated).collect()
+}
+
+struct BoundVisitor<'a> {
+    params: &'a [&'a Ident],
+    used: Vec<&'a Ident>,
+    associated: Vec<TypePath>,
+}
+
+impl BoundVisitor<'_> {
+    fn push_associated(&mut self, ty: &TypePath) {
+        let tokens = ty.to_token_stream().to_string();
+        let seen = self
+            .associated
+            .iter()
+            .any(|path| path.to_token_stream().to_string() == tokens);
+        if !seen {
+            self.associated.push(ty.clone());
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for BoundVisitor<'_> {
+    fn visit_type_path(&mut self, ty: &'ast TypePath) {
+        if let Some(qself) = &ty.qself {
+            // `<T as Trait>::Out`, or `<T::Item as Trait>::Out` further down.
+            if mentions_param(&qself.ty, self.params) {
+                self.push_associated(ty);
+                return;
+            }
+        } else {
+            let segments = &ty.path.segments;
+            let first = &segments.first().unwrap().ident;
+            if let Some(param) = self.params.iter().find(|param| **param == first) {
+                if segments.len() == 1 {
+                    if !self.used.contains(param) {
+                        self.used.push(*param);
+                    }
+                } else {
+                    self.push_associated(ty);
+                }
+                return;
+            }
+            // PhantomData<T> is Debug whatever T is.
+            if segments.last().unwrap().ident == "PhantomData" {
+                return;
+            }
+        }
+        visit::visit_type_path(self, ty);
+    }
+
+    // `dyn Trait<T>` gets its Debug impl, if any, from the trait itself.
+    fn visit_type_trait_object(&mut self, _ty: &'ast TypeTraitObject) {}
+
+    // `fn(T) -> U` is Debug whatever T and U are.
+    fn visit_type_bare_fn(&mut self, _ty: &'ast TypeBareFn) {}
+}
+
+fn mentions_param(ty: &Type, params: &[&Ident]) -> bool {
+    struct Mentions<'a> {
+        params: &'a [&'a Ident],
+        found: bool,
+    }
+
+    impl<'ast> Visit<'ast> for Mentions<'_> {
+        fn visit_type_path(&mut self, ty: &'ast TypePath) {
+            if ty.qself.is_none() {
+                let first = &ty.path.segments.first().unwrap().ident;
+                if self.params.contains(&first) {
+                    self.found = true;
+                    return;
+                }
+            }
+            visit::visit_type_path(self, ty);
+        }
+    }
+
+    let mut visitor = Mentions {
+        params,
+        found: false,
+    };
+    visitor.visit_type(ty);
+    visitor.found
+}
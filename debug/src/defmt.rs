@@ -0,0 +1,129 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{parse_quote, DeriveInput, Ident, LitStr};
+
+use crate::attr::TypeAttrs;
+use crate::layout::{self, Segment};
+use crate::{bound, debug_truncated, debug_with, truncated, uses_with};
+
+/// `defmt::Format`, writing each arm with a single `defmt::write!`. Fields print
+/// through their own Format impls, with the display hints of their format
+/// strings, except for those printed by a `with` function or truncated, which go
+/// through `defmt::Debug2Format`.
+///
+/// Bounds are always inferred, since those given to Debug by hand do not carry
+/// over to Format.
+pub fn expand_defmt(input: &DeriveInput, type_attrs: &TypeAttrs) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let formatter = Ident::new("f", Span::mixed_site());
+
+    let arms = layout::arms(input, type_attrs)?;
+    let mut printed = Vec::new();
+    let mut debug_printed = Vec::new();
+    let arms = arms.iter().map(|arm| {
+        let mut format = String::new();
+        let mut args = Vec::new();
+        for segment in &arm.segments {
+            match segment {
+                Segment::Text(text) => {
+                    format.push_str(&text.replace('{', "{{").replace('}', "}}"));
+                }
+                Segment::Value {
+                    binding,
+                    ty,
+                    spec,
+                    span,
+                } => {
+                    match spec.as_deref() {
+                        None | Some("") => format.push_str("{}"),
+                        Some(spec) if is_display_hint(spec) => {
+                            format.push_str(&format!("{{:{}}}", spec))
+                        }
+                        Some(spec) => {
+                            return Err(syn::Error::new(
+                                *span,
+                                format!(
+                                "`{{:{}}}` has no defmt display hint, expected `{{}}`, `{{:?}}`, \
+                                     or `x`, `X`, `b` or `o` with optional `#` and zero padding \
+                                     as in `{{:#06x}}`",
+                                spec
+                            ),
+                            ))
+                        }
+                    }
+                    args.push(quote!(#binding));
+                    printed.push(ty);
+                }
+                Segment::With { binding, with } => {
+                    format.push_str("{}");
+                    args.push(quote!(::defmt::Debug2Format(&DebugWith(#binding, #with))));
+                }
+                Segment::Truncated { binding, ty, len } => {
+                    format.push_str("{}");
+                    let truncated = truncated(binding, ty, len);
+                    args.push(quote!(::defmt::Debug2Format(&#truncated)));
+                    debug_printed.push(ty);
+                }
+                Segment::Flatten(binding) => {
+                    return Err(syn::Error::new(
+                        binding.span(),
+                        "`debug(flatten)` cannot be used with the defmt feature",
+                    ))
+                }
+            }
+        }
+        let pattern = &arm.pattern;
+        let format = LitStr::new(&format, Span::call_site());
+        Ok(quote! {
+            #pattern => ::defmt::write!(#formatter, #format #(, #args)*),
+        })
+    });
+    let arms: Vec<TokenStream> = arms.collect::<syn::Result<_>>()?;
+    let body = if arms.is_empty() {
+        quote!(match *self {})
+    } else {
+        quote!(match self { #(#arms)* })
+    };
+
+    let mut generics = input.generics.clone();
+    let predicates = bound::infer(&input.generics, printed, &parse_quote!(::defmt::Format));
+    let debug_predicates = bound::infer(
+        &input.generics,
+        debug_printed,
+        &parse_quote!(::core::fmt::Debug),
+    );
+    let where_clause = generics.make_where_clause();
+    where_clause.predicates.extend(predicates);
+    where_clause.predicates.extend(debug_predicates);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let debug_with = if uses_with(&input.data)? {
+        debug_with()
+    } else {
+        quote!()
+    };
+    let debug_truncated = debug_truncated(&input.data)?;
+
+    Ok(quote! {
+        impl #impl_generics ::defmt::Format for #ident #ty_generics #where_clause {
+            fn format(&self, #formatter: ::defmt::Formatter<'_>) {
+                #debug_with
+                #(#debug_truncated)*
+                #body
+            }
+        }
+    })
+}
+
+/// `[#][0width][?|x|X|b|o]`, the Rust format specs which are display hints of
+/// `defmt::write!` as well.
+fn is_display_hint(spec: &str) -> bool {
+    let mut spec = spec.strip_prefix('#').unwrap_or(spec);
+    if let Some(width) = spec.strip_prefix('0') {
+        spec = width.trim_start_matches(|ch: char| ch.is_ascii_digit());
+        if spec.len() == width.len() {
+            return false;
+        }
+    }
+    matches!(spec, "" | "?" | "x" | "X" | "b" | "o")
+}
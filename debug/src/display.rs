@@ -0,0 +1,220 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Attribute, Data, DeriveInput, Fields, Ident, Lit, LitStr, Meta, NestedMeta, Type};
+
+use crate::bound;
+use crate::format::{Arg, Piece, Spec, Template};
+
+pub fn expand_display(input: DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let formatter = Ident::new("f", Span::mixed_site());
+
+    if let (Data::Enum(_), Some(template)) = (&input.data, parse_display_attr(&input.attrs)?) {
+        return Err(syn::Error::new(
+            template.span(),
+            "an enum is displayed through the templates of its variants, put \
+             `#[display(\"...\")]` on those instead",
+        ));
+    }
+
+    let mut used = Vec::new();
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let template = parse_display_attr(&input.attrs)?.ok_or_else(|| {
+                syn::Error::new(ident.span(), "missing `#[display(\"...\")]` template")
+            })?;
+            let arm = display_arm(quote!(Self), &data.fields, &template, &formatter, &mut used)?;
+            quote!(match self { #arm })
+        }
+        Data::Enum(data) if data.variants.is_empty() => quote!(match *self {}),
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let variant_ident = &variant.ident;
+                    let path = quote!(Self::#variant_ident);
+                    match parse_display_attr(&variant.attrs)? {
+                        Some(template) => {
+                            display_arm(path, &variant.fields, &template, &formatter, &mut used)
+                        }
+                        // A unit variant without a template displays as its name.
+                        None if variant.fields.is_empty() => {
+                            let name = variant_ident.unraw().to_string();
+                            Ok(quote!(#path { .. } => #formatter.write_str(#name),))
+                        }
+                        None => Err(syn::Error::new(
+                            variant_ident.span(),
+                            "missing `#[display(\"...\")]` template",
+                        )),
+                    }
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote!(match self { #(#arms)* })
+        }
+        Data::Union(_) => return Err(syn::Error::new(ident.span(), "expects struct or enum")),
+    };
+
+    // Each field referenced by a template needs the formatting trait its
+    // placeholder asks for.
+    let mut traits: Vec<&str> = used.iter().map(|(_, format_trait)| *format_trait).collect();
+    traits.sort_unstable();
+    traits.dedup();
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for format_trait in traits {
+        let types = used
+            .iter()
+            .filter(|(_, used_trait)| *used_trait == format_trait)
+            .map(|(ty, _)| *ty);
+        let trait_ident = Ident::new(format_trait, Span::call_site());
+        let trait_path = syn::parse_quote!(::core::fmt::#trait_ident);
+        where_clause
+            .predicates
+            .extend(bound::infer(&input.generics, types, &trait_path));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+            fn fmt(&self, #formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #body
+            }
+        }
+    })
+}
+
+/// One match arm writing a struct or an enum variant through its template.
+///
+/// Placeholders name fields, `{name}` for named fields and `{0}` for tuple
+/// fields. Each referenced field is bound as `_0`, `_1`, ... by its position
+/// and passed to `write!` as a named argument of that name, so that raw
+/// identifiers and fields shadowing each other are not a concern.
+fn display_arm<'a>(
+    path: TokenStream,
+    fields: &'a Fields,
+    template: &LitStr,
+    formatter: &Ident,
+    used: &mut Vec<(&'a Type, &'static str)>,
+) -> syn::Result<TokenStream> {
+    let parsed = Template::parse(&template.value())
+        .map_err(|message| syn::Error::new(template.span(), message))?;
+
+    let mut format = String::new();
+    let mut referenced = vec![false; fields.len()];
+    for piece in &parsed.pieces {
+        let (arg, spec) = match piece {
+            Piece::Text(text) => {
+                format.push_str(&text.replace('{', "{{").replace('}', "}}"));
+                continue;
+            }
+            Piece::Arg { arg, spec } => (arg, spec),
+        };
+        let index = field_index(arg, fields, template)?;
+        let parsed_spec =
+            Spec::parse(spec).map_err(|message| syn::Error::new(template.span(), message))?;
+
+        // Widths and precisions taken from fields, as in `{name:>width$}`,
+        // refer to their bindings just like the value.
+        let mut spec: Vec<char> = spec.chars().collect();
+        for (count, range) in parsed_spec.counts.into_iter().rev() {
+            let count_index = field_index(&count, fields, template)?;
+            referenced[count_index] = true;
+            let binding: Vec<char> = format!("_{}$", count_index).chars().collect();
+            spec.splice(range, binding);
+        }
+        let spec: String = spec.into_iter().collect();
+
+        let field = fields.iter().nth(index).unwrap();
+        used.push((&field.ty, parsed_spec.format_trait));
+        referenced[index] = true;
+        if spec.is_empty() {
+            format.push_str(&format!("{{_{}}}", index));
+        } else {
+            format.push_str(&format!("{{_{}:{}}}", index, spec));
+        }
+    }
+
+    let bindings: Vec<Ident> = (0..fields.len()).map(|i| format_ident!("_{}", i)).collect();
+    let pattern = match fields {
+        Fields::Named(_) => {
+            let field_patterns = fields
+                .iter()
+                .zip(&bindings)
+                .zip(&referenced)
+                .filter(|(_, referenced)| **referenced)
+                .map(|((field, binding), _)| {
+                    let ident = &field.ident;
+                    quote!(#ident: #binding)
+                });
+            quote!(#path { #(#field_patterns,)* .. })
+        }
+        Fields::Unnamed(_) => {
+            let field_patterns = bindings
+                .iter()
+                .zip(&referenced)
+                .map(|(binding, referenced)| {
+                    if *referenced {
+                        quote!(#binding)
+                    } else {
+                        quote!(_)
+                    }
+                });
+            quote!(#path(#(#field_patterns),*))
+        }
+        Fields::Unit => quote!(#path),
+    };
+    let args = bindings
+        .iter()
+        .zip(&referenced)
+        .filter(|(_, referenced)| **referenced)
+        .map(|(binding, _)| quote!(, #binding = #binding));
+    let format = LitStr::new(&format, template.span());
+
+    Ok(quote! {
+        #pattern => ::core::write!(#formatter, #format #(#args)*),
+    })
+}
+
+/// The position of the field a placeholder, or the width or precision of one,
+/// refers to.
+fn field_index(arg: &Arg, fields: &Fields, template: &LitStr) -> syn::Result<usize> {
+    let index = match (arg, fields) {
+        (Arg::Named(name), Fields::Named(_)) => fields
+            .iter()
+            .position(|field| field.ident.as_ref().unwrap().unraw() == name),
+        (Arg::Index(index), Fields::Unnamed(_)) if *index < fields.len() => Some(*index),
+        (Arg::Next, _) => {
+            return Err(syn::Error::new(
+                template.span(),
+                "placeholders must name a field, as in `{name}` or `{0}`",
+            ))
+        }
+        _ => None,
+    };
+    index.ok_or_else(|| {
+        let arg = match arg {
+            Arg::Named(name) => name.clone(),
+            Arg::Index(index) => index.to_string(),
+            Arg::Next => unreachable!(),
+        };
+        syn::Error::new(template.span(), format!("no field `{}` to display", arg))
+    })
+}
+
+/// The template given by `#[display("...")]`.
+fn parse_display_attr(attrs: &[Attribute]) -> syn::Result<Option<LitStr>> {
+    let mut template = None;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("display")) {
+        let meta = attr.parse_meta()?;
+        match &meta {
+            Meta::List(list) if list.nested.len() == 1 => match list.nested.first() {
+                Some(NestedMeta::Lit(Lit::Str(str))) => template = Some(str.clone()),
+                _ => return Err(syn::Error::new_spanned(meta, "expected `display(\"...\")`")),
+            },
+            _ => return Err(syn::Error::new_spanned(meta, "expected `display(\"...\")`")),
+        }
+    }
+    Ok(template)
+}
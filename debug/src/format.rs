@@ -0,0 +1,185 @@
+use std::ops::Range;
+
+/// A format string as accepted by `format_args!`, split into literal text and
+/// placeholders.
+pub struct Template {
+    pub pieces: Vec<Piece>,
+}
+
+pub enum Piece {
+    /// Literal text, with `{{` and `}}` already unescaped.
+    Text(String),
+    /// A `{arg:spec}` placeholder.
+    Arg { arg: Arg, spec: String },
+}
+
+pub enum Arg {
+    /// `{}`: the next positional argument.
+    Next,
+    /// `{0}`: a positional argument by index.
+    Index(usize),
+    /// `{name}`: a named argument.
+    Named(String),
+}
+
+impl Template {
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut pieces = Vec::new();
+        let mut text = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '}' => return Err("unmatched `}` in format string".to_owned()),
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(ch) => placeholder.push(ch),
+                            None => return Err("unterminated `{` in format string".to_owned()),
+                        }
+                    }
+                    if !text.is_empty() {
+                        pieces.push(Piece::Text(std::mem::take(&mut text)));
+                    }
+                    let (arg, spec) = match placeholder.split_once(':') {
+                        Some((arg, spec)) => (arg.trim(), spec.to_owned()),
+                        None => (placeholder.trim(), String::new()),
+                    };
+                    let arg = if arg.is_empty() {
+                        Arg::Next
+                    } else if let Ok(index) = arg.parse() {
+                        Arg::Index(index)
+                    } else if is_identifier(arg) {
+                        Arg::Named(arg.to_owned())
+                    } else {
+                        return Err(format!("invalid argument `{}` in format string", arg));
+                    };
+                    pieces.push(Piece::Arg { arg, spec });
+                }
+                ch => text.push(ch),
+            }
+        }
+        if !text.is_empty() {
+            pieces.push(Piece::Text(text));
+        }
+        Ok(Template { pieces })
+    }
+}
+
+/// The spec of a placeholder, the part after the `:`, as in
+/// `[[fill]align][sign]['#']['0'][width]['.' precision]type`.
+pub struct Spec {
+    /// The formatting trait the spec asks for, such as `Display` for `{}`,
+    /// `Debug` for `{:?}` or `LowerHex` for `{:#x}`.
+    pub format_trait: &'static str,
+    /// Arguments the width and precision are taken from, as in `{:1$}`,
+    /// `{:.prec$}` or `{:.*}`, in the order they are consumed, along with the
+    /// characters of the spec referring to them.
+    pub counts: Vec<(Arg, Range<usize>)>,
+}
+
+impl Spec {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid format spec in `{{:{}}}`", spec);
+        let chars: Vec<char> = spec.chars().collect();
+        let mut i = 0;
+        if chars.len() >= 2 && matches!(chars[1], '<' | '^' | '>') {
+            i = 2;
+        } else if matches!(chars.first(), Some('<' | '^' | '>')) {
+            i = 1;
+        }
+        if matches!(chars.get(i), Some('+' | '-')) {
+            i += 1;
+        }
+        if chars.get(i) == Some(&'#') {
+            i += 1;
+        }
+        // `{:0$}` is a width taken from argument 0, not the `0` flag.
+        if chars.get(i) == Some(&'0') && chars.get(i + 1) != Some(&'$') {
+            i += 1;
+        }
+
+        let mut counts = Vec::new();
+        if let Some((count, end)) = count(&chars, i) {
+            counts.extend(count.map(|arg| (arg, i..end)));
+            i = end;
+        }
+        if chars.get(i) == Some(&'.') {
+            i += 1;
+            if chars.get(i) == Some(&'*') {
+                counts.push((Arg::Next, i..i + 1));
+                i += 1;
+            } else {
+                let (count, end) = count(&chars, i).ok_or_else(invalid)?;
+                counts.extend(count.map(|arg| (arg, i..end)));
+                i = end;
+            }
+        }
+
+        let rest: String = chars[i..].iter().collect();
+        let format_trait = match rest.as_str() {
+            "" => "Display",
+            "?" | "x?" | "X?" => "Debug",
+            "x" => "LowerHex",
+            "X" => "UpperHex",
+            "o" => "Octal",
+            "b" => "Binary",
+            "e" => "LowerExp",
+            "E" => "UpperExp",
+            "p" => "Pointer",
+            _ if is_identifier(&rest) => {
+                return Err(format!("unknown format trait in `{{:{}}}`", spec))
+            }
+            _ => return Err(invalid()),
+        };
+        Ok(Spec {
+            format_trait,
+            counts,
+        })
+    }
+}
+
+/// A width or precision starting at `chars[i]`: a literal number, which refers
+/// to no argument, or an argument followed by `$`. Returns where it ends.
+fn count(chars: &[char], i: usize) -> Option<(Option<Arg>, usize)> {
+    let digits = chars[i..]
+        .iter()
+        .take_while(|ch| ch.is_ascii_digit())
+        .count();
+    if digits > 0 {
+        let number: String = chars[i..i + digits].iter().collect();
+        let index = number.parse().ok()?;
+        return match chars.get(i + digits) {
+            Some('$') => Some((Some(Arg::Index(index)), i + digits + 1)),
+            _ => Some((None, i + digits)),
+        };
+    }
+    let len = chars[i..]
+        .iter()
+        .take_while(|ch| **ch == '_' || ch.is_alphanumeric())
+        .count();
+    let word: String = chars[i..i + len].iter().collect();
+    match chars.get(i + len) {
+        Some('$') if is_identifier(&word) => Some((Some(Arg::Named(word)), i + len + 1)),
+        // Not a count; whatever is there is left to the type.
+        _ => None,
+    }
+}
+
+fn is_identifier(arg: &str) -> bool {
+    let mut chars = arg.chars();
+    match chars.next() {
+        Some(ch) if ch == '_' || ch.is_alphabetic() => {}
+        _ => return false,
+    }
+    chars.all(|ch| ch == '_' || ch.is_alphanumeric())
+}
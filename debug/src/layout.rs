@@ -0,0 +1,268 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Data, DeriveInput, ExprPath, Fields, Ident, LitInt, LitStr, Type};
+
+use crate::attr::{field_name, parse_field_attrs, parse_variant_attrs, TypeAttrs};
+use crate::format::{Arg, Piece, Template};
+
+/// What one match arm prints, flattened into a sequence of text and field
+/// values, for formatting traits which have no `debug_struct` style builders of
+/// their own.
+///
+/// The layout is the one of `{:?}` on the Debug impl, only never pretty-printed
+/// and without leaving out empty fields at runtime.
+pub struct Arm {
+    pub pattern: TokenStream,
+    pub segments: Vec<Segment>,
+}
+
+pub enum Segment {
+    /// Literal text, as written out, without any `{{` escapes.
+    Text(String),
+    /// A field value, with the spec of the placeholder printing it or `None`
+    /// when printed the way the trait prints values by default, and the span of
+    /// the format string the spec comes from.
+    Value {
+        binding: Ident,
+        ty: Type,
+        spec: Option<String>,
+        span: Span,
+    },
+    /// A field printed through a `#[debug(with = "...")]` function.
+    #[cfg_attr(not(feature = "defmt"), allow(dead_code))]
+    With { binding: Ident, with: ExprPath },
+    /// A `#[debug(flatten)]` field, whose own fields only the Debug impl can
+    /// reach.
+    Flatten(Ident),
+    /// A field cut short by `#[debug(truncate = N)]`.
+    #[cfg_attr(not(feature = "defmt"), allow(dead_code))]
+    Truncated {
+        binding: Ident,
+        ty: Type,
+        len: LitInt,
+    },
+}
+
+/// One arm per variant, or the single arm of a struct. An empty enum has none.
+pub fn arms(input: &DeriveInput, type_attrs: &TypeAttrs) -> syn::Result<Vec<Arm>> {
+    match &input.data {
+        Data::Struct(data) => Ok(vec![arm(
+            quote!(Self),
+            &input.ident,
+            &data.fields,
+            None,
+            type_attrs,
+        )?]),
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                let format = parse_variant_attrs(variant)?.format;
+                arm(
+                    quote!(Self::#variant_ident),
+                    variant_ident,
+                    &variant.fields,
+                    format,
+                    type_attrs,
+                )
+            })
+            .collect(),
+        Data::Union(_) => Err(syn::Error::new(
+            input.ident.span(),
+            "expects struct or enum",
+        )),
+    }
+}
+
+fn arm(
+    path: TokenStream,
+    ident: &Ident,
+    fields: &Fields,
+    format: Option<LitStr>,
+    type_attrs: &TypeAttrs,
+) -> syn::Result<Arm> {
+    let bindings: Vec<Ident> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => ident.clone(),
+            None => format_ident!("_{}", i),
+        })
+        .collect();
+    let mut segments = Segments::default();
+
+    if let Some(format) = format {
+        let template =
+            Template::parse(&format.value()).map_err(|err| syn::Error::new(format.span(), err))?;
+        let mut next = 0;
+        let mut referenced = vec![false; bindings.len()];
+        for piece in template.pieces {
+            let (arg, spec) = match piece {
+                Piece::Text(text) => {
+                    segments.text(&text);
+                    continue;
+                }
+                Piece::Arg { arg, spec } => (arg, spec),
+            };
+            let index = match (&arg, fields) {
+                (Arg::Named(name), Fields::Named(_)) => {
+                    bindings.iter().position(|binding| binding.unraw() == name)
+                }
+                (Arg::Next, Fields::Unnamed(_)) => {
+                    next += 1;
+                    Some(next - 1)
+                }
+                (Arg::Index(index), Fields::Unnamed(_)) => Some(*index),
+                _ => None,
+            };
+            let index = index
+                .filter(|index| *index < bindings.len())
+                .ok_or_else(|| {
+                    syn::Error::new(
+                        ident.span(),
+                        "format string refers to no field of this variant",
+                    )
+                })?;
+            referenced[index] = true;
+            segments.0.push(Segment::Value {
+                binding: bindings[index].clone(),
+                ty: fields.iter().nth(index).unwrap().ty.clone(),
+                spec: Some(spec),
+                span: format.span(),
+            });
+        }
+        // Only the fields the format string refers to are bound.
+        let patterns = bindings
+            .iter()
+            .zip(&referenced)
+            .map(|(binding, referenced)| {
+                if *referenced {
+                    quote!(#binding)
+                } else {
+                    quote!(_)
+                }
+            });
+        let pattern = match fields {
+            Fields::Named(_) => {
+                let bindings = bindings
+                    .iter()
+                    .zip(&referenced)
+                    .filter(|(_, referenced)| **referenced)
+                    .map(|(binding, _)| binding);
+                quote!(#path { #(#bindings,)* .. })
+            }
+            Fields::Unnamed(_) => quote!(#path(#(#patterns),*)),
+            Fields::Unit => quote!(#path),
+        };
+        return Ok(Arm {
+            pattern,
+            segments: segments.0,
+        });
+    }
+
+    let (open, close) = match fields {
+        Fields::Named(_) => (" { ", " }"),
+        _ => ("(", ")"),
+    };
+    segments.text(&ident.unraw().to_string());
+    let mut patterns = Vec::new();
+    let mut printed = 0;
+    let mut skipped = false;
+    for (field, binding) in fields.iter().zip(&bindings) {
+        let field_attrs = parse_field_attrs(field)?;
+        let redact = field_attrs.redact.as_ref().or(type_attrs.redact.as_ref());
+
+        let binds_value = !field_attrs.skip && redact.is_none();
+        patterns.push(match (&field.ident, binds_value) {
+            (Some(ident), true) => quote!(#ident),
+            (Some(ident), false) => quote!(#ident: _),
+            (None, true) => quote!(#binding),
+            (None, false) => quote!(_),
+        });
+        if field_attrs.skip {
+            skipped = true;
+            continue;
+        }
+        if field_attrs.flatten {
+            segments.0.push(Segment::Flatten(binding.clone()));
+            continue;
+        }
+
+        segments.text(if printed == 0 { open } else { ", " });
+        printed += 1;
+        if field.ident.is_some() {
+            segments.text(&format!(
+                "{}: ",
+                field_name(field, &field_attrs, type_attrs)
+            ));
+        }
+        match (redact, field_attrs.with, field_attrs.format) {
+            (Some(placeholder), _, _) => segments.text(placeholder),
+            (None, Some(with), _) => segments.0.push(Segment::With {
+                binding: binding.clone(),
+                with,
+            }),
+            (None, None, Some(format)) => {
+                let template = Template::parse(&format.value())
+                    .map_err(|err| syn::Error::new(format.span(), err))?;
+                for piece in template.pieces {
+                    match piece {
+                        Piece::Text(text) => segments.text(&text),
+                        Piece::Arg { spec, .. } => segments.0.push(Segment::Value {
+                            binding: binding.clone(),
+                            ty: field.ty.clone(),
+                            spec: Some(spec),
+                            span: format.span(),
+                        }),
+                    }
+                }
+            }
+            (None, None, None) => segments.0.push(match field_attrs.truncate {
+                Some(len) => Segment::Truncated {
+                    binding: binding.clone(),
+                    ty: field.ty.clone(),
+                    len,
+                },
+                None => Segment::Value {
+                    binding: binding.clone(),
+                    ty: field.ty.clone(),
+                    spec: None,
+                    span: binding.span(),
+                },
+            }),
+        }
+    }
+    if type_attrs.non_exhaustive && skipped {
+        segments.text(if printed == 0 { open } else { ", " });
+        segments.text("..");
+        printed += 1;
+    }
+    if printed > 0 {
+        segments.text(close);
+    }
+
+    let pattern = match fields {
+        Fields::Named(_) => quote!(#path { #(#patterns),* }),
+        Fields::Unnamed(_) => quote!(#path(#(#patterns),*)),
+        Fields::Unit => quote!(#path),
+    };
+    Ok(Arm {
+        pattern,
+        segments: segments.0,
+    })
+}
+
+/// Segments with adjacent text merged.
+#[derive(Default)]
+struct Segments(Vec<Segment>);
+
+impl Segments {
+    fn text(&mut self, text: &str) {
+        match self.0.last_mut() {
+            Some(Segment::Text(last)) => last.push_str(text),
+            _ => self.0.push(Segment::Text(text.to_owned())),
+        }
+    }
+}
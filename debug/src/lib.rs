@@ -1,8 +1,856 @@
-use proc_macro::TokenStream;
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Field, Fields, GenericArgument, Ident,
+    LitInt, LitStr, PathArguments, Type, WhereClause,
+};
 
-#[proc_macro_derive(CustomDebug)]
-pub fn derive(input: TokenStream) -> TokenStream {
-    let _ = input;
+use crate::attr::{
+    field_name, parse_field_attrs, parse_type_attrs, parse_variant_attrs, placeholder_traits,
+    TypeAttrs,
+};
+use crate::format::Arg;
 
-    unimplemented!()
+mod attr;
+mod bound;
+#[cfg(feature = "defmt")]
+mod defmt;
+mod display;
+mod format;
+#[cfg(any(feature = "defmt", feature = "ufmt"))]
+mod layout;
+#[cfg(feature = "ufmt")]
+mod ufmt;
+#[cfg(feature = "valuable")]
+mod valuable;
+
+#[proc_macro_derive(CustomDebug, attributes(debug))]
+pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    proc_macro::TokenStream::from(expand(input))
+}
+
+#[proc_macro_derive(CustomDisplay, attributes(display))]
+pub fn derive_display(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match display::expand_display(input) {
+        Ok(expand) => proc_macro::TokenStream::from(expand),
+        Err(err) => proc_macro::TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn expand(input: DeriveInput) -> TokenStream {
+    match expand_debug(input) {
+        Ok(expand) => expand,
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+/// A field along with the formatting traits the format string of its variant
+/// asks of it, if the variant has one.
+type VariantField<'a> = (&'a Field, Option<Vec<&'static str>>);
+
+fn expand_debug(input: DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let type_attrs = parse_type_attrs(&input.attrs)?;
+    let formatter = Ident::new("f", Span::mixed_site());
+
+    let (body, fields): (TokenStream, Vec<VariantField>) = match &input.data {
+        Data::Struct(data) => {
+            let arm = debug_arm(
+                quote!(Self),
+                ident,
+                &data.fields,
+                None,
+                &type_attrs,
+                &formatter,
+            )?;
+            let fields = data.fields.iter().map(|field| (field, None)).collect();
+            (quote!(match self { #arm }), fields)
+        }
+        Data::Enum(data) if data.variants.is_empty() => (quote!(match *self {}), Vec::new()),
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let variant_ident = &variant.ident;
+                    let format = parse_variant_attrs(variant)?.format;
+                    let format = format.as_ref().map(LitStr::value);
+                    debug_arm(
+                        quote!(Self::#variant_ident),
+                        variant_ident,
+                        &variant.fields,
+                        format,
+                        &type_attrs,
+                        &formatter,
+                    )
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            let mut fields = Vec::new();
+            for variant in &data.variants {
+                let format = parse_variant_attrs(variant)?.format;
+                let mut traits = match &format {
+                    Some(format) => Some(variant_format_traits(format, &variant.fields)?),
+                    None => None,
+                };
+                for (i, field) in variant.fields.iter().enumerate() {
+                    let traits = traits.as_mut().map(|traits| std::mem::take(&mut traits[i]));
+                    fields.push((field, traits));
+                }
+            }
+            (quote!(match self { #(#arms)* }), fields)
+        }
+        Data::Union(_) => return Err(syn::Error::new(ident.span(), "expects struct or enum")),
+    };
+
+    #[cfg(feature = "defmt")]
+    let defmt_impl = if type_attrs.defmt {
+        defmt::expand_defmt(&input, &type_attrs)?
+    } else {
+        quote!()
+    };
+    #[cfg(not(feature = "defmt"))]
+    let defmt_impl = quote!();
+    #[cfg(feature = "ufmt")]
+    let ufmt_impl = if type_attrs.ufmt {
+        ufmt::expand_ufmt(&input, &type_attrs)?
+    } else {
+        quote!()
+    };
+    #[cfg(not(feature = "ufmt"))]
+    let ufmt_impl = quote!();
+    #[cfg(feature = "valuable")]
+    let valuable_impl = if type_attrs.valuable {
+        valuable::expand_valuable(&input, &type_attrs)?
+    } else {
+        quote!()
+    };
+    #[cfg(not(feature = "valuable"))]
+    let valuable_impl = quote!();
+
+    let mut generics = input.generics.clone();
+    let predicates = match &type_attrs.bound {
+        Some(bound) => bound.iter().cloned().collect(),
+        None => {
+            // Fields which are skipped, redacted or printed by a function of
+            // their own do not need to implement Debug, fields given a format
+            // string need the formatting traits its placeholders ask for, and
+            // fields given bounds of their own are not looked into.
+            let mut printed: Vec<(&Type, &str)> = Vec::new();
+            let mut field_bounds = Vec::new();
+            for (field, variant_traits) in fields {
+                let field_attrs = parse_field_attrs(field)?;
+                if let Some(bound) = field_attrs.bound {
+                    field_bounds.extend(bound);
+                } else if let Some(traits) = variant_traits {
+                    printed.extend(
+                        traits
+                            .into_iter()
+                            .map(|format_trait| (&field.ty, format_trait)),
+                    );
+                } else if !field_attrs.skip
+                    && field_attrs.redact.is_none()
+                    && field_attrs.with.is_none()
+                    && type_attrs.redact.is_none()
+                {
+                    match &field_attrs.format {
+                        Some(format) => printed.extend(
+                            placeholder_traits(format)?
+                                .into_iter()
+                                .map(|(_, format_trait)| (&field.ty, format_trait)),
+                        ),
+                        None => printed.push((&field.ty, "Debug")),
+                    }
+                }
+            }
+            let mut traits: Vec<&str> = printed
+                .iter()
+                .map(|(_, format_trait)| *format_trait)
+                .collect();
+            traits.sort_unstable();
+            traits.dedup();
+            let mut predicates = Vec::new();
+            for format_trait in traits {
+                let types = printed
+                    .iter()
+                    .filter(|(_, used_trait)| *used_trait == format_trait)
+                    .map(|(ty, _)| *ty);
+                let trait_ident = Ident::new(format_trait, Span::call_site());
+                let trait_path = parse_quote!(::core::fmt::#trait_ident);
+                predicates.extend(bound::infer(&input.generics, types, &trait_path));
+            }
+            for bound in field_bounds {
+                let tokens = bound.to_token_stream().to_string();
+                if !predicates
+                    .iter()
+                    .any(|predicate| predicate.to_token_stream().to_string() == tokens)
+                {
+                    predicates.push(bound);
+                }
+            }
+            predicates
+        }
+    };
+    generics.make_where_clause().predicates.extend(predicates);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let debug_with = if uses_with(&input.data)? {
+        debug_with()
+    } else {
+        quote!()
+    };
+    let debug_truncated = debug_truncated(&input.data)?;
+    let helpers = quote! {
+        #debug_with
+        #(#debug_truncated)*
+    };
+
+    // Writing ourselves through `{:?}` gets a formatter without the alternate
+    // flag, which the nested fields then inherit.
+    let compact = if type_attrs.compact {
+        quote! {
+            if #formatter.alternate() {
+                return ::core::write!(#formatter, "{:?}", self);
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let guards = recursion_guards(&type_attrs, &formatter);
+
+    let flatten = match &input.data {
+        _ if !type_attrs.flattenable => quote!(),
+        Data::Struct(data) if matches!(data.fields, Fields::Named(_)) => {
+            flatten_method(&input, &data.fields, &type_attrs, where_clause, &helpers)?
+        }
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`debug(flattenable)` requires a struct with named fields",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::core::fmt::Debug for #ident #ty_generics #where_clause {
+            fn fmt(&self, #formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #helpers
+                #compact
+                #guards
+                #body
+            }
+        }
+
+        #flatten
+        #defmt_impl
+        #ufmt_impl
+        #valuable_impl
+    })
+}
+
+/// The formatting traits a variant's format string asks of each of its fields.
+fn variant_format_traits(format: &LitStr, fields: &Fields) -> syn::Result<Vec<Vec<&'static str>>> {
+    let mut traits = vec![Vec::new(); fields.len()];
+    for (arg, format_trait) in placeholder_traits(format)? {
+        let index = match arg {
+            Arg::Named(name) => fields.iter().position(|field| {
+                field
+                    .ident
+                    .as_ref()
+                    .is_some_and(|ident| ident.unraw() == name)
+            }),
+            Arg::Index(index) => Some(index),
+            Arg::Next => unreachable!(),
+        };
+        // Unknown fields have already been reported along with the attribute.
+        if let Some(traits) = index.and_then(|index| traits.get_mut(index)) {
+            traits.push(format_trait);
+        }
+    }
+    Ok(traits)
+}
+
+/// One match arm printing a struct or an enum variant. The fields are bound by
+/// reference under their own names, or `_0`, `_1`, ... for tuple fields.
+///
+/// A variant given a format string of its own is written with it; named fields
+/// are in scope for inline arguments like `{code}`, tuple fields are passed as
+/// positional arguments.
+fn debug_arm(
+    path: TokenStream,
+    ident: &Ident,
+    fields: &Fields,
+    format: Option<String>,
+    type_attrs: &TypeAttrs,
+    formatter: &Ident,
+) -> syn::Result<TokenStream> {
+    let bindings: Vec<Ident> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => ident.clone(),
+            None => format_ident!("_{}", i),
+        })
+        .collect();
+
+    if let Some(format) = format {
+        let pattern = match fields {
+            Fields::Named(_) => quote!(#path { #(#bindings),* }),
+            Fields::Unnamed(_) => quote!(#path(#(#bindings),*)),
+            Fields::Unit => quote!(#path),
+        };
+        let args = match fields {
+            Fields::Unnamed(_) => quote!(#(, #bindings)*),
+            _ => quote!(),
+        };
+        return Ok(quote! {
+            #[allow(unused_variables)]
+            #pattern => ::core::write!(#formatter, #format #args),
+        });
+    }
+
+    let ArmFields {
+        patterns,
+        entries,
+        skipped,
+    } = arm_fields(fields, &bindings, type_attrs)?;
+
+    let name = ident.unraw().to_string();
+    let (pattern, builder) = match fields {
+        Fields::Named(_) => (
+            quote!(#path { #(#patterns),* }),
+            quote!(#formatter.debug_struct(#name)),
+        ),
+        Fields::Unnamed(_) => (
+            quote!(#path(#(#patterns),*)),
+            quote!(#formatter.debug_tuple(#name)),
+        ),
+        Fields::Unit => {
+            return Ok(quote! {
+                #path => #formatter.write_str(#name),
+            })
+        }
+    };
+
+    let body = if entries.iter().all(|entry| entry.is_static()) {
+        let entries = entries.iter().map(|entry| match entry {
+            Entry::Field { call, .. } => call,
+            Entry::Flatten { .. } => unreachable!(),
+        });
+        let finish = if type_attrs.non_exhaustive && skipped {
+            quote!(.finish_non_exhaustive())
+        } else {
+            quote!(.finish())
+        };
+        quote! {
+            #builder
+                #(#entries)*
+                #finish
+        }
+    } else {
+        // Whether fields are left out is only known at runtime.
+        let builder_ident = Ident::new("builder", Span::mixed_site());
+        if type_attrs.non_exhaustive {
+            let omitted = Ident::new("omitted", Span::mixed_site());
+            let statements = statements(&entries, &builder_ident, Some(&omitted));
+            quote! {{
+                let #builder_ident = &mut #builder;
+                let mut #omitted = #skipped;
+                #(#statements)*
+                if #omitted {
+                    #builder_ident.finish_non_exhaustive()
+                } else {
+                    #builder_ident.finish()
+                }
+            }}
+        } else {
+            let statements = statements(&entries, &builder_ident, None);
+            quote! {{
+                let #builder_ident = &mut #builder;
+                #(#statements)*
+                #builder_ident.finish()
+            }}
+        }
+    };
+    Ok(quote! {
+        #pattern => #body,
+    })
+}
+
+/// How the fields of one struct or variant are matched and printed.
+struct ArmFields {
+    patterns: Vec<TokenStream>,
+    entries: Vec<Entry>,
+    /// Whether some field is left out by `#[debug(skip)]`.
+    skipped: bool,
+}
+
+enum Entry {
+    /// A `.field(...)` call, left out when `is_empty` holds at runtime.
+    Field {
+        is_empty: Option<TokenStream>,
+        call: TokenStream,
+    },
+    /// A `#[debug(flatten)]` field, whose own fields are added by the
+    /// `__debug_flatten` method of its type, or reported at the span of the
+    /// type if it has none.
+    Flatten { binding: Ident, span: Span },
+}
+
+impl Entry {
+    /// Whether the entry can be chained onto the builder as is.
+    fn is_static(&self) -> bool {
+        matches!(self, Entry::Field { is_empty: None, .. })
+    }
+}
+
+fn arm_fields(
+    fields: &Fields,
+    bindings: &[Ident],
+    type_attrs: &TypeAttrs,
+) -> syn::Result<ArmFields> {
+    let mut patterns = Vec::new();
+    let mut entries = Vec::new();
+    let mut skipped = false;
+    for (field, binding) in fields.iter().zip(bindings) {
+        let field_attrs = parse_field_attrs(field)?;
+        let redact = field_attrs.redact.as_ref().or(type_attrs.redact.as_ref());
+
+        // Fields whose value is not printed are not bound at all.
+        let binds_value = !field_attrs.skip && redact.is_none();
+        patterns.push(match (&field.ident, binds_value) {
+            (Some(ident), true) => quote!(#ident),
+            (Some(ident), false) => quote!(#ident: _),
+            (None, true) => quote!(#binding),
+            (None, false) => quote!(_),
+        });
+        if field_attrs.skip {
+            skipped = true;
+            continue;
+        }
+        if field_attrs.flatten {
+            entries.push(Entry::Flatten {
+                binding: binding.clone(),
+                span: field.ty.span(),
+            });
+            continue;
+        }
+
+        let is_empty = if type_attrs.omit_empty && binds_value {
+            if is_option(&field.ty) {
+                Some(quote!(#binding.is_none()))
+            } else if is_collection(&field.ty) {
+                Some(quote!(#binding.is_empty()))
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+        let format = field_attrs.format.as_ref().map(LitStr::value);
+        let value = match (redact, &field_attrs.with, &format) {
+            (Some(placeholder), _, _) => quote!(&::core::format_args!("{}", #placeholder)),
+            (None, Some(with), _) => quote!(&DebugWith(#binding, #with)),
+            (None, None, Some(format)) => quote!(&::core::format_args!(#format, #binding)),
+            (None, None, None) => match &field_attrs.truncate {
+                Some(len) => {
+                    let truncated = truncated(binding, &field.ty, len);
+                    quote!(&#truncated)
+                }
+                None => quote!(#binding),
+            },
+        };
+        let call = match &field.ident {
+            Some(_) => {
+                let name = field_name(field, &field_attrs, type_attrs);
+                quote!(.field(#name, #value))
+            }
+            None => quote!(.field(#value)),
+        };
+        entries.push(Entry::Field { is_empty, call });
+    }
+    Ok(ArmFields {
+        patterns,
+        entries,
+        skipped,
+    })
+}
+
+/// The entries as statements on a builder. Fields left out at runtime set
+/// `omitted`, if given.
+fn statements(entries: &[Entry], builder: &Ident, omitted: Option<&Ident>) -> Vec<TokenStream> {
+    entries
+        .iter()
+        .map(|entry| match (entry, omitted) {
+            (
+                Entry::Field {
+                    is_empty: None,
+                    call,
+                },
+                _,
+            ) => quote!(#builder #call;),
+            (
+                Entry::Field {
+                    is_empty: Some(is_empty),
+                    call,
+                },
+                Some(omitted),
+            ) => quote! {
+                if #is_empty {
+                    #omitted = true;
+                } else {
+                    #builder #call;
+                }
+            },
+            (
+                Entry::Field {
+                    is_empty: Some(is_empty),
+                    call,
+                },
+                None,
+            ) => quote! {
+                if !#is_empty {
+                    #builder #call;
+                }
+            },
+            // The method is inherent to types marked `#[debug(flattenable)]`,
+            // so it takes precedence over the one of the local trait, which
+            // only exists to explain what is missing on other types.
+            (Entry::Flatten { binding, span }, _) => quote_spanned! {*span=>
+                {
+                    #[diagnostic::on_unimplemented(
+                        message = "`{Self}` cannot be flattened into another struct",
+                        label = "not `#[debug(flattenable)]`",
+                        note = "add `#[debug(flattenable)]` to the definition of `{Self}`"
+                    )]
+                    trait DebugFlattenable {}
+
+                    #[allow(dead_code)]
+                    trait DebugFlatten {
+                        fn __debug_flatten(&self, _: &mut ::core::fmt::DebugStruct<'_, '_>)
+                        where
+                            Self: DebugFlattenable,
+                        {
+                        }
+                    }
+
+                    impl<T: ?::core::marker::Sized> DebugFlatten for T {}
+
+                    #binding.__debug_flatten(#builder);
+                }
+            },
+        })
+        .collect()
+}
+
+/// A hidden inherent method adding the fields of a struct with named fields to
+/// the `DebugStruct` of another, for fields of this type marked
+/// `#[debug(flatten)]`. Only generated for `#[debug(flattenable)]`, as it is
+/// part of the API of the type.
+fn flatten_method(
+    input: &DeriveInput,
+    fields: &Fields,
+    type_attrs: &TypeAttrs,
+    where_clause: Option<&WhereClause>,
+    helpers: &TokenStream,
+) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let bindings: Vec<Ident> = fields
+        .iter()
+        .map(|field| field.ident.clone().unwrap())
+        .collect();
+    let ArmFields {
+        patterns, entries, ..
+    } = arm_fields(fields, &bindings, type_attrs)?;
+    let builder = Ident::new("builder", Span::mixed_site());
+    let statements = statements(&entries, &builder, None);
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            #vis fn __debug_flatten(&self, #builder: &mut ::core::fmt::DebugStruct<'_, '_>) {
+                #helpers
+                match self {
+                    Self { #(#patterns),* } => {
+                        #(#statements)*
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Whether the type is spelled as an `Option`.
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments.last().unwrap().ident == "Option",
+        _ => false,
+    }
+}
+
+/// Whether the type is spelled as a standard collection, string or slice,
+/// which all have an `is_empty` method.
+fn is_collection(ty: &Type) -> bool {
+    const COLLECTIONS: &[&str] = &[
+        "Vec",
+        "VecDeque",
+        "LinkedList",
+        "BinaryHeap",
+        "HashMap",
+        "HashSet",
+        "BTreeMap",
+        "BTreeSet",
+        "String",
+    ];
+    match ty {
+        Type::Path(path) => {
+            let ident = &path.path.segments.last().unwrap().ident;
+            COLLECTIONS.iter().any(|collection| ident == collection)
+        }
+        Type::Reference(reference) => match &*reference.elem {
+            Type::Path(path) => path.path.is_ident("str"),
+            Type::Slice(_) => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn uses_with(data: &Data) -> syn::Result<bool> {
+    let fields: Vec<&Field> = match data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data.variants.iter().flat_map(|v| &v.fields).collect(),
+        Data::Union(_) => Vec::new(),
+    };
+    for field in fields {
+        if parse_field_attrs(field)?.with.is_some() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Adaptor giving a field printed by `#[debug(with = "...")]` a Debug impl
+/// which calls the function.
+fn debug_with() -> TokenStream {
+    quote! {
+        struct DebugWith<'a, T: ?Sized>(
+            &'a T,
+            fn(&T, &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result,
+        );
+
+        impl<T: ?Sized> ::core::fmt::Debug for DebugWith<'_, T> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                (self.1)(self.0, f)
+            }
+        }
+    }
+}
+
+/// How a `#[debug(truncate = N)]` field is cut short.
+#[derive(Clone, Copy, PartialEq)]
+enum Truncation {
+    /// After N of the items it iterates over by reference.
+    Items,
+    /// After N characters, for types spelled as a string.
+    Chars,
+}
+
+impl Truncation {
+    fn of(ty: &Type) -> Self {
+        if is_string(ty) {
+            Truncation::Chars
+        } else {
+            Truncation::Items
+        }
+    }
+}
+
+/// Whether the type is spelled as a string, such as `String`, `&str`,
+/// `Box<str>` or `Cow<str>`, which all implement `AsRef<str>`.
+fn is_string(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.path.is_ident("str") => true,
+        Type::Path(path) => {
+            let segment = path.path.segments.last().unwrap();
+            if segment.ident == "String" {
+                return true;
+            }
+            let args = match &segment.arguments {
+                PathArguments::AngleBracketed(args) => &args.args,
+                _ => return false,
+            };
+            ["Box", "Rc", "Arc", "Cow"]
+                .iter()
+                .any(|pointer| segment.ident == pointer)
+                && args.iter().any(|arg| match arg {
+                    GenericArgument::Type(Type::Path(path)) => path.path.is_ident("str"),
+                    _ => false,
+                })
+        }
+        Type::Reference(reference) => is_string(&reference.elem),
+        _ => false,
+    }
+}
+
+/// The adaptor printing a `#[debug(truncate = N)]` field bound by reference.
+fn truncated(binding: &Ident, ty: &Type, len: &LitInt) -> TokenStream {
+    match Truncation::of(ty) {
+        Truncation::Items => quote!(DebugTruncated(#binding, #len)),
+        Truncation::Chars => quote! {
+            DebugTruncatedStr(::core::convert::AsRef::<str>::as_ref(#binding), #len)
+        },
+    }
+}
+
+/// Adaptors for the kinds of truncation the fields use. Either ends with the
+/// number of items or characters left out, as in `[1, 2, ... (998 more)]` or
+/// `"abc"... (23 more)`; any other iterable prints as a list.
+fn debug_truncated(data: &Data) -> syn::Result<Vec<TokenStream>> {
+    let fields: Vec<&Field> = match data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data.variants.iter().flat_map(|v| &v.fields).collect(),
+        Data::Union(_) => Vec::new(),
+    };
+    let mut used = Vec::new();
+    for field in fields {
+        if parse_field_attrs(field)?.truncate.is_some() {
+            let truncation = Truncation::of(&field.ty);
+            if !used.contains(&truncation) {
+                used.push(truncation);
+            }
+        }
+    }
+    Ok(used
+        .into_iter()
+        .map(|truncation| match truncation {
+            Truncation::Items => quote! {
+                struct DebugTruncated<'a, T: ?Sized>(&'a T, usize);
+
+                impl<'a, T: ?Sized> ::core::fmt::Debug for DebugTruncated<'a, T>
+                where
+                    &'a T: ::core::iter::IntoIterator,
+                    <&'a T as ::core::iter::IntoIterator>::Item: ::core::fmt::Debug,
+                {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        let mut list = f.debug_list();
+                        let mut items = ::core::iter::IntoIterator::into_iter(self.0);
+                        let shown = ::core::iter::Iterator::by_ref(&mut items);
+                        list.entries(::core::iter::Iterator::take(shown, self.1));
+                        let more = ::core::iter::Iterator::count(items);
+                        if more > 0 {
+                            list.entry(&::core::format_args!("... ({} more)", more));
+                        }
+                        list.finish()
+                    }
+                }
+            },
+            Truncation::Chars => quote! {
+                struct DebugTruncatedStr<'a>(&'a str, usize);
+
+                impl ::core::fmt::Debug for DebugTruncatedStr<'_> {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        match ::core::iter::Iterator::nth(&mut self.0.char_indices(), self.1) {
+                            ::core::option::Option::Some((end, _)) => {
+                                ::core::fmt::Debug::fmt(&self.0[..end], f)?;
+                                let more = ::core::iter::Iterator::count(self.0[end..].chars());
+                                ::core::write!(f, "... ({} more)", more)
+                            }
+                            ::core::option::Option::None => ::core::fmt::Debug::fmt(self.0, f),
+                        }
+                    }
+                }
+            },
+        })
+        .collect())
+}
+
+/// Checks on entering `fmt` for `max_depth` and `detect_cycles`, keeping count
+/// in thread-locals of the impl, which guards undo again on leaving. Being per
+/// impl, they only see the type nested within itself, which is where recursion
+/// comes from.
+fn recursion_guards(type_attrs: &TypeAttrs, formatter: &Ident) -> TokenStream {
+    let mut guards = TokenStream::new();
+    if let Some(max_depth) = type_attrs.max_depth {
+        let max_depth = Literal::usize_unsuffixed(max_depth);
+        let depth = Ident::new("depth", Span::mixed_site());
+        guards.extend(quote! {
+            ::std::thread_local! {
+                static DEBUG_DEPTH: ::core::cell::Cell<usize> =
+                    const { ::core::cell::Cell::new(0) };
+            }
+            struct LeaveDepth;
+            impl ::core::ops::Drop for LeaveDepth {
+                fn drop(&mut self) {
+                    DEBUG_DEPTH.with(|cell| cell.set(cell.get() - 1));
+                }
+            }
+            let #depth = DEBUG_DEPTH.with(::core::cell::Cell::get);
+            if #depth >= #max_depth {
+                return #formatter.write_str("…");
+            }
+            DEBUG_DEPTH.with(|cell| cell.set(cell.get() + 1));
+            let _leave_depth = LeaveDepth;
+        });
+    }
+    if type_attrs.detect_cycles {
+        // The type name tells apart the instantiations of a generic type,
+        // which share the thread-local, at the same address.
+        let visiting = Ident::new("visiting", Span::mixed_site());
+        guards.extend(quote! {
+            ::std::thread_local! {
+                static DEBUG_VISITING: ::core::cell::RefCell<
+                    ::std::vec::Vec<(*const (), &'static str)>,
+                > = const { ::core::cell::RefCell::new(::std::vec::Vec::new()) };
+            }
+            struct LeaveVisiting;
+            impl ::core::ops::Drop for LeaveVisiting {
+                fn drop(&mut self) {
+                    DEBUG_VISITING.with(|stack| stack.borrow_mut().pop());
+                }
+            }
+            let #visiting = (
+                self as *const Self as *const (),
+                ::core::any::type_name::<Self>(),
+            );
+            if DEBUG_VISITING.with(|stack| stack.borrow().contains(&#visiting)) {
+                return #formatter.write_str("<cycle>");
+            }
+            DEBUG_VISITING.with(|stack| stack.borrow_mut().push(#visiting));
+            let _leave_visiting = LeaveVisiting;
+        });
+    }
+    guards
+}
+
+#[cfg(test)]
+#[test]
+fn expand_snapshots() {
+    snapshot::Snapshots::new("tests/expand")
+        .derive("CustomDebug", |input| expand(syn::parse2(input).unwrap()));
+    #[cfg(feature = "std")]
+    snapshot::Snapshots::new("tests/expand/std")
+        .derive("CustomDebug", |input| expand(syn::parse2(input).unwrap()));
+    #[cfg(feature = "defmt")]
+    snapshot::Snapshots::new("tests/expand/defmt")
+        .derive("CustomDebug", |input| expand(syn::parse2(input).unwrap()));
+    #[cfg(feature = "ufmt")]
+    snapshot::Snapshots::new("tests/expand/ufmt")
+        .derive("CustomDebug", |input| expand(syn::parse2(input).unwrap()));
+    #[cfg(feature = "valuable")]
+    snapshot::Snapshots::new("tests/expand/valuable")
+        .derive("CustomDebug", |input| expand(syn::parse2(input).unwrap()));
+    snapshot::Snapshots::new("tests/expand/display").derive("CustomDisplay", |input| {
+        match display::expand_display(syn::parse2(input).unwrap()) {
+            Ok(expand) => expand,
+            Err(err) => err.to_compile_error(),
+        }
+    });
 }
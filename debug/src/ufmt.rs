@@ -0,0 +1,164 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{parse_quote, DeriveInput, Ident, LitStr, Type};
+
+use crate::attr::TypeAttrs;
+use crate::bound;
+use crate::layout::{self, Segment};
+
+/// `ufmt::uDebug`, writing each arm as a sequence of `write_str` calls for the
+/// text and `uDebug::fmt` or `uwrite!` calls for the fields. Fields with a
+/// format string are written through `uwrite!` with its placeholders, which
+/// only takes `{}`, `{:?}`, `{:#?}` and hex like `{:#06x}`, so other specs are
+/// rejected at the format string.
+///
+/// `with` functions and truncation write to a `core::fmt::Formatter` and so
+/// cannot be used, and bounds are always inferred.
+pub fn expand_ufmt(input: &DeriveInput, type_attrs: &TypeAttrs) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let formatter = Ident::new("f", Span::mixed_site());
+
+    let mut debug: Vec<&Type> = Vec::new();
+    let mut display: Vec<&Type> = Vec::new();
+    let mut hex: Vec<&Type> = Vec::new();
+    let layout = layout::arms(input, type_attrs)?;
+    let mut arms = Vec::new();
+    for arm in &layout {
+        let mut statements = Vec::new();
+        for segment in &arm.segments {
+            statements.push(match segment {
+                Segment::Text(text) => quote!(#formatter.write_str(#text)?;),
+                Segment::Value {
+                    binding,
+                    ty,
+                    spec: None,
+                    ..
+                } => {
+                    debug.push(ty);
+                    quote!(::ufmt::uDebug::fmt(#binding, #formatter)?;)
+                }
+                Segment::Value {
+                    binding,
+                    ty,
+                    spec: Some(spec),
+                    span,
+                } => {
+                    // uDisplayHex is only implemented for the integers
+                    // themselves, not references to them.
+                    let mut value = quote!(#binding);
+                    match spec.as_str() {
+                        "" => display.push(ty),
+                        "?" | "#?" => debug.push(ty),
+                        _ if is_hex_spec(spec) => {
+                            hex.push(ty);
+                            value = quote!(*#binding);
+                        }
+                        _ => {
+                            return Err(syn::Error::new(
+                                *span,
+                                format!(
+                                    "`{{:{}}}` is not supported by ufmt, expected `{{}}`, \
+                                     `{{:?}}`, `{{:#?}}` or hex as in `{{:#06x}}`",
+                                    spec
+                                ),
+                            ))
+                        }
+                    }
+                    let format = if spec.is_empty() {
+                        "{}".to_owned()
+                    } else {
+                        format!("{{:{}}}", spec)
+                    };
+                    let format = LitStr::new(&format, Span::call_site());
+                    quote!(::ufmt::uwrite!(#formatter, #format, #value)?;)
+                }
+                Segment::With { with, .. } => {
+                    return Err(syn::Error::new_spanned(
+                        with,
+                        "`debug(with = \"...\")` cannot be used with the ufmt feature",
+                    ))
+                }
+                Segment::Truncated { len, .. } => {
+                    return Err(syn::Error::new(
+                        len.span(),
+                        "`debug(truncate = N)` cannot be used with the ufmt feature",
+                    ))
+                }
+                Segment::Flatten(binding) => {
+                    return Err(syn::Error::new(
+                        binding.span(),
+                        "`debug(flatten)` cannot be used with the ufmt feature",
+                    ))
+                }
+            });
+        }
+        let pattern = &arm.pattern;
+        arms.push(quote! {
+            #pattern => {
+                #(#statements)*
+            }
+        });
+    }
+    let body = if arms.is_empty() {
+        quote!(match *self {})
+    } else {
+        quote! {
+            match self { #(#arms)* }
+            ::core::result::Result::Ok(())
+        }
+    };
+
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    where_clause.predicates.extend(bound::infer(
+        &input.generics,
+        debug,
+        &parse_quote!(::ufmt::uDebug),
+    ));
+    where_clause.predicates.extend(bound::infer(
+        &input.generics,
+        display,
+        &parse_quote!(::ufmt::uDisplay),
+    ));
+    where_clause.predicates.extend(bound::infer(
+        &input.generics,
+        hex,
+        &parse_quote!(::ufmt::uDisplayHex),
+    ));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // The writer's type parameter must not clash with those of the type.
+    let mut writer = String::from("W");
+    while input
+        .generics
+        .type_params()
+        .any(|param| param.ident == writer)
+    {
+        writer.push('_');
+    }
+    let writer = Ident::new(&writer, Span::call_site());
+
+    Ok(quote! {
+        impl #impl_generics ::ufmt::uDebug for #ident #ty_generics #where_clause {
+            fn fmt<#writer>(
+                &self,
+                #formatter: &mut ::ufmt::Formatter<'_, #writer>,
+            ) -> ::core::result::Result<(), #writer::Error>
+            where
+                #writer: ::ufmt::uWrite + ?::core::marker::Sized,
+            {
+                #body
+            }
+        }
+    })
+}
+
+/// `[#][0][width](x|X)`, the only specs besides `?` and `#?` that `uwrite!`
+/// understands.
+fn is_hex_spec(spec: &str) -> bool {
+    let spec = spec.strip_prefix('#').unwrap_or(spec);
+    match spec.strip_suffix(['x', 'X']) {
+        Some(width) => width.chars().all(|ch| ch.is_ascii_digit()),
+        None => false,
+    }
+}
@@ -0,0 +1,223 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{parse_quote, Data, DeriveInput, Fields, Ident, Type};
+
+use crate::attr::{field_name, parse_field_attrs, TypeAttrs};
+use crate::bound;
+
+/// `valuable::Valuable` together with `Structable` or `Enumerable`, which hand
+/// the fields to a visitor as typed values instead of formatted text. This is
+/// how `tracing` records structured values, through `tracing::field::valuable`.
+///
+/// Skipped fields are left out and redacted ones are visited as their
+/// placeholder string. Format strings and truncation only concern the Debug
+/// output; the values are visited as they are. Bounds are always inferred.
+pub fn expand_valuable(input: &DeriveInput, type_attrs: &TypeAttrs) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let name = ident.unraw().to_string();
+    let visit = Ident::new("visit", Span::mixed_site());
+
+    // The variant each set of fields belongs to, if any.
+    let visited: Vec<(Option<&Ident>, VisitedFields)> = match &input.data {
+        Data::Struct(data) => vec![(
+            None,
+            visited_fields(quote!(Self), &data.fields, type_attrs)?,
+        )],
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                let fields =
+                    visited_fields(quote!(Self::#variant_ident), &variant.fields, type_attrs)?;
+                Ok((Some(variant_ident), fields))
+            })
+            .collect::<syn::Result<_>>()?,
+        Data::Union(_) => return Err(syn::Error::new(ident.span(), "expects struct or enum")),
+    };
+
+    let mut generics = input.generics.clone();
+    let types = visited.iter().flat_map(|(_, fields)| &fields.types);
+    let predicates = bound::infer(&input.generics, types, &parse_quote!(::valuable::Valuable));
+    generics.make_where_clause().predicates.extend(predicates);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let visit_arms = visited.iter().map(|(_, fields)| {
+        let pattern = &fields.pattern;
+        let call = fields.visit(&visit);
+        quote!(#pattern => #call,)
+    });
+    let visit_body = if visited.is_empty() {
+        quote!(match *self {})
+    } else {
+        quote!(match self { #(#visit_arms)* })
+    };
+
+    let (value, definition) = match &input.data {
+        Data::Struct(_) => {
+            let fields = &visited[0].1;
+            let (consts, def) = match &fields.names {
+                Some(names) => (
+                    quote!(const FIELDS: &[::valuable::NamedField<'static>] = #names;),
+                    quote!(::valuable::Fields::Named(FIELDS)),
+                ),
+                None => (quote!(), fields.definition()),
+            };
+            let definition = quote! {
+                impl #impl_generics ::valuable::Structable for #ident #ty_generics #where_clause {
+                    fn definition(&self) -> ::valuable::StructDef<'_> {
+                        #consts
+                        ::valuable::StructDef::new_static(#name, #def)
+                    }
+                }
+            };
+            (quote!(::valuable::Value::Structable(self)), definition)
+        }
+        _ => {
+            let variant_defs = visited.iter().map(|(variant_ident, fields)| {
+                let variant_name = variant_ident.unwrap().unraw().to_string();
+                let def = fields.definition();
+                quote!(::valuable::VariantDef::new(#variant_name, #def))
+            });
+            let variants = quote! {
+                const VARIANTS: &[::valuable::VariantDef<'static>] = &[#(#variant_defs),*];
+            };
+            let variant_arms = visited.iter().enumerate().map(|(i, (variant_ident, _))| {
+                quote!(Self::#variant_ident { .. } => ::valuable::Variant::Static(&VARIANTS[#i]),)
+            });
+            let variant_body = if visited.is_empty() {
+                quote!(match *self {})
+            } else {
+                quote! {
+                    #variants
+                    match self { #(#variant_arms)* }
+                }
+            };
+            let definition = quote! {
+                impl #impl_generics ::valuable::Enumerable for #ident #ty_generics #where_clause {
+                    fn definition(&self) -> ::valuable::EnumDef<'_> {
+                        #variants
+                        ::valuable::EnumDef::new_static(#name, VARIANTS)
+                    }
+
+                    fn variant(&self) -> ::valuable::Variant<'_> {
+                        #variant_body
+                    }
+                }
+            };
+            (quote!(::valuable::Value::Enumerable(self)), definition)
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::valuable::Valuable for #ident #ty_generics #where_clause {
+            fn as_value(&self) -> ::valuable::Value<'_> {
+                #value
+            }
+
+            fn visit(&self, #visit: &mut dyn ::valuable::Visit) {
+                #visit_body
+            }
+        }
+
+        #definition
+    })
+}
+
+/// The fields of a struct or variant which get visited.
+struct VisitedFields {
+    pattern: TokenStream,
+    /// `&[NamedField::new("a"), ...]` for named fields.
+    names: Option<TokenStream>,
+    values: Vec<TokenStream>,
+    types: Vec<Type>,
+}
+
+impl VisitedFields {
+    /// The `valuable::Fields` of the definition, usable in a const context.
+    fn definition(&self) -> TokenStream {
+        match &self.names {
+            Some(names) => quote!(::valuable::Fields::Named(#names)),
+            None => {
+                let len = self.values.len();
+                quote!(::valuable::Fields::Unnamed(#len))
+            }
+        }
+    }
+
+    /// Hands the values to the visitor.
+    fn visit(&self, visit: &Ident) -> TokenStream {
+        let values = &self.values;
+        match &self.names {
+            Some(names) => quote! {{
+                const FIELDS: &[::valuable::NamedField<'static>] = #names;
+                #visit.visit_named_fields(&::valuable::NamedValues::new(FIELDS, &[#(#values),*]))
+            }},
+            None => quote!(#visit.visit_unnamed_fields(&[#(#values),*])),
+        }
+    }
+}
+
+fn visited_fields(
+    path: TokenStream,
+    fields: &Fields,
+    type_attrs: &TypeAttrs,
+) -> syn::Result<VisitedFields> {
+    let mut patterns = Vec::new();
+    let mut names = Vec::new();
+    let mut values = Vec::new();
+    let mut types = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let field_attrs = parse_field_attrs(field)?;
+        let redact = field_attrs.redact.as_ref().or(type_attrs.redact.as_ref());
+        let binding = match &field.ident {
+            Some(ident) => ident.clone(),
+            None => format_ident!("_{}", i),
+        };
+
+        let binds_value = !field_attrs.skip && redact.is_none();
+        patterns.push(match (&field.ident, binds_value) {
+            (Some(ident), true) => quote!(#ident),
+            (Some(ident), false) => quote!(#ident: _),
+            (None, true) => quote!(#binding),
+            (None, false) => quote!(_),
+        });
+        if field_attrs.skip {
+            continue;
+        }
+        if field_attrs.flatten {
+            return Err(syn::Error::new(
+                binding.span(),
+                "`debug(flatten)` cannot be used with the valuable feature",
+            ));
+        }
+
+        if field.ident.is_some() {
+            let name = field_name(field, &field_attrs, type_attrs);
+            names.push(quote!(::valuable::NamedField::new(#name)));
+        }
+        values.push(match redact {
+            Some(placeholder) => quote!(::valuable::Value::String(#placeholder)),
+            None => {
+                types.push(field.ty.clone());
+                quote!(::valuable::Valuable::as_value(#binding))
+            }
+        });
+    }
+
+    let (pattern, names) = match fields {
+        Fields::Named(_) => (
+            quote!(#path { #(#patterns),* }),
+            Some(quote!(&[#(#names),*])),
+        ),
+        Fields::Unnamed(_) => (quote!(#path(#(#patterns),*)), None),
+        Fields::Unit => (quote!(#path), None),
+    };
+    Ok(VisitedFields {
+        pattern,
+        names,
+        values,
+        types,
+    })
+}
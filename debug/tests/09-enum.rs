@@ -0,0 +1,55 @@
+// Enums are supported too. Each variant is printed the way a struct of the same
+// shape would be: through debug_struct for variants with named fields, through
+// debug_tuple for tuple variants and as just its name for unit variants. Field
+// attributes work on the fields of variants exactly as on struct fields.
+//
+// A variant may also carry a #[debug = "..."] attribute of its own, which
+// replaces its output altogether. Named fields of the variant can be referred
+// to by name in that format string, and tuple fields are passed as positional
+// arguments.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub enum Error<E> {
+    Io {
+        path: &'static str,
+        #[debug = "{:#x}"]
+        code: u32,
+    },
+    Parse(usize, #[debug = "line {}"] usize),
+    Other(E),
+    #[debug = "timed out after {secs}s"]
+    Timeout { secs: u64 },
+    #[debug = "<{0}>"]
+    Status(u16),
+    Unknown,
+}
+
+#[derive(CustomDebug)]
+pub enum Never {}
+
+fn main() {
+    let err: Error<&str> = Error::Io {
+        path: "/etc/passwd",
+        code: 13,
+    };
+    assert_eq!(format!("{:?}", err), r#"Io { path: "/etc/passwd", code: 0xd }"#);
+
+    let err: Error<&str> = Error::Parse(4, 2);
+    assert_eq!(format!("{:?}", err), "Parse(4, line 2)");
+
+    let err: Error<&str> = Error::Other("boom");
+    assert_eq!(format!("{:?}", err), r#"Other("boom")"#);
+
+    let err: Error<&str> = Error::Timeout { secs: 30 };
+    assert_eq!(format!("{:?}", err), "timed out after 30s");
+
+    let err: Error<&str> = Error::Status(404);
+    assert_eq!(format!("{:?}", err), "<404>");
+
+    let err: Error<&str> = Error::Unknown;
+    assert_eq!(format!("{:?}", err), "Unknown");
+
+    let _ = |never: Never| format!("{:?}", never);
+}
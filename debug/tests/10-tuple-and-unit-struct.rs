@@ -0,0 +1,23 @@
+// Tuple structs are printed through debug_tuple and unit structs as just their
+// name, the same as the standard library's derive(Debug) does.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Bitmask(&'static str, #[debug = "0b{:08b}"] u8);
+
+#[derive(CustomDebug)]
+pub struct Wrapper<T>(T);
+
+#[derive(CustomDebug)]
+pub struct Unit;
+
+fn main() {
+    let mask = Bitmask("F", 0b00011100);
+    assert_eq!(format!("{:?}", mask), r#"Bitmask("F", 0b00011100)"#);
+
+    assert_eq!(format!("{:?}", Wrapper(1)), "Wrapper(1)");
+    assert_eq!(format!("{:#?}", Wrapper(1)), "Wrapper(\n    1,\n)");
+
+    assert_eq!(format!("{:?}", Unit), "Unit");
+}
@@ -0,0 +1,86 @@
+// Secrets such as tokens and passwords must not end up in logs through Debug.
+//
+// A field marked #[debug(skip)] is left out of the output altogether, and one
+// marked #[debug(redact)] is printed as a placeholder, "[REDACTED]" unless
+// given as #[debug(redact = "...")]. Putting #[debug(redact)] on the type
+// redacts every field.
+//
+// Since their values are never printed, skipped and redacted fields contribute
+// no Debug bounds to the generated impl.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+
+#[derive(CustomDebug)]
+pub struct Credentials<T, U> {
+    user: &'static str,
+    #[debug(redact)]
+    password: T,
+    #[debug(redact = "***")]
+    token: U,
+    #[debug(skip)]
+    salt: u64,
+}
+
+#[derive(CustomDebug)]
+pub struct Hashed(&'static str, #[debug(skip)] [u8; 4]);
+
+#[derive(CustomDebug)]
+#[debug(redact)]
+pub struct Secret {
+    key: &'static str,
+    nonce: u32,
+}
+
+#[derive(CustomDebug)]
+pub enum Auth<S> {
+    Basic {
+        user: &'static str,
+        #[debug(redact)]
+        password: S,
+    },
+    Bearer(#[debug(redact = "<token>")] S),
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    // Does not implement Debug.
+    struct NotDebug;
+
+    assert_debug::<Credentials<NotDebug, NotDebug>>();
+    assert_debug::<Auth<NotDebug>>();
+
+    let credentials = Credentials {
+        user: "admin",
+        password: "hunter2",
+        token: "abcdef",
+        salt: 42,
+    };
+    assert_eq!(
+        format!("{:?}", credentials),
+        r#"Credentials { user: "admin", password: [REDACTED], token: *** }"#,
+    );
+
+    let hashed = Hashed("sha1", [0; 4]);
+    assert_eq!(format!("{:?}", hashed), r#"Hashed("sha1")"#);
+
+    let secret = Secret {
+        key: "0123",
+        nonce: 7,
+    };
+    assert_eq!(
+        format!("{:?}", secret),
+        "Secret { key: [REDACTED], nonce: [REDACTED] }",
+    );
+
+    let auth = Auth::Basic {
+        user: "admin",
+        password: "hunter2",
+    };
+    assert_eq!(
+        format!("{:?}", auth),
+        r#"Basic { user: "admin", password: [REDACTED] }"#,
+    );
+    assert_eq!(format!("{:?}", Auth::Bearer("abcdef")), "Bearer(<token>)");
+}
@@ -0,0 +1,70 @@
+// Sometimes a format string is not enough to print a field the way we want.
+// #[debug(with = "path::to_fn")] names a function with the signature
+//
+//     fn(&T, &mut fmt::Formatter) -> fmt::Result
+//
+// which is called to print the field in place of its Debug impl. The field
+// type then does not need to implement Debug itself, so no bound is inferred
+// for it.
+
+use derive_debug::CustomDebug;
+use std::fmt::{self, Debug};
+use std::time::Duration;
+
+mod fmt_helpers {
+    use std::fmt;
+    use std::time::Duration;
+
+    pub fn hex(bytes: &Vec<u8>, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in bytes {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+
+    pub fn millis(duration: &Duration, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}ms", duration.as_millis())
+    }
+}
+
+pub struct Opaque;
+
+fn opaque<T>(_: &T, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("<opaque>")
+}
+
+#[derive(CustomDebug)]
+pub struct Packet<T> {
+    #[debug(with = "fmt_helpers::hex")]
+    payload: Vec<u8>,
+    #[debug(with = "fmt_helpers::millis")]
+    latency: Duration,
+    #[debug(with = "opaque")]
+    context: T,
+}
+
+#[derive(CustomDebug)]
+pub enum Frame {
+    Data(#[debug(with = "fmt_helpers::hex")] Vec<u8>),
+    Ping,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    assert_debug::<Packet<Opaque>>();
+
+    let packet = Packet {
+        payload: vec![0xde, 0xad, 0xbe, 0xef],
+        latency: Duration::from_millis(25),
+        context: Opaque,
+    };
+    assert_eq!(
+        format!("{:?}", packet),
+        "Packet { payload: deadbeef, latency: 25ms, context: <opaque> }",
+    );
+
+    let frame = Frame::Data(vec![1, 2]);
+    assert_eq!(format!("{:?}", frame), "Data(0102)");
+    assert_eq!(format!("{:?}", Frame::Ping), "Ping");
+}
@@ -0,0 +1,78 @@
+// The crate also provides a companion derive for Display. The template given
+// in #[display("...")] on the type is a format string whose placeholders name
+// the fields to interpolate, {name} for named fields and {0} for tuple fields,
+// with the usual format specs after a colon. A width or precision can be taken
+// from a field as well, as in {name:>width$}.
+//
+// Enums take a template per variant. Unit variants without one are displayed
+// as their name.
+//
+// Type parameters get bounds for the formatting trait each placeholder uses,
+// such as Display for {name} and Debug for {name:?}.
+
+use derive_debug::CustomDisplay;
+
+#[derive(CustomDisplay)]
+#[display("{name} ({id})")]
+pub struct User {
+    id: u32,
+    name: &'static str,
+    #[allow(dead_code)]
+    email: &'static str,
+}
+
+#[derive(CustomDisplay)]
+#[display("#{0:04} {{{1}}}")]
+pub struct Ticket(u32, &'static str);
+
+#[derive(CustomDisplay)]
+#[display("[{label:>width$}]")]
+pub struct Cell {
+    label: &'static str,
+    width: usize,
+}
+
+#[derive(CustomDisplay)]
+#[display("{0:.1$}")]
+pub struct Price(f64, usize);
+
+#[derive(CustomDisplay)]
+pub enum Event<T> {
+    #[display("{user} logged in")]
+    Login { user: T },
+    #[display("{0:?} left: {1}")]
+    Leave(T, &'static str),
+    #[display("code {0:#x}")]
+    Error(u16),
+    Shutdown,
+}
+
+fn main() {
+    let user = User {
+        id: 7,
+        name: "ferris",
+        email: "ferris@example.com",
+    };
+    assert_eq!(user.to_string(), "ferris (7)");
+
+    assert_eq!(Ticket(42, "open").to_string(), "#0042 {open}");
+
+    let cell = Cell {
+        label: "ok",
+        width: 5,
+    };
+    assert_eq!(cell.to_string(), "[   ok]");
+    assert_eq!(Price(1.5, 2).to_string(), "1.50");
+
+    let event = Event::Login { user: "ferris" };
+    assert_eq!(event.to_string(), "ferris logged in");
+
+    let event = Event::Leave("ferris", "timeout");
+    assert_eq!(event.to_string(), r#""ferris" left: timeout"#);
+
+    let event: Event<&str> = Event::Error(255);
+    assert_eq!(event.to_string(), "code 0xff");
+
+    let event: Event<&str> = Event::Shutdown;
+    assert_eq!(event.to_string(), "Shutdown");
+}
@@ -0,0 +1,35 @@
+// Field names in a display template, including those a width or precision is
+// taken from, are checked when the derive expands, and mistakes are reported
+// at the template. An enum has a template per variant rather than one of its
+// own.
+
+use derive_debug::CustomDisplay;
+
+#[derive(CustomDisplay)]
+#[display("{name} ({uid})")]
+pub struct User {
+    id: u32,
+    name: &'static str,
+}
+
+#[derive(CustomDisplay)]
+pub enum Event {
+    #[display("logged in")]
+    Login,
+    Leave(u32),
+}
+
+#[derive(CustomDisplay)]
+#[display("{name:>width$}")]
+pub struct Label {
+    name: &'static str,
+}
+
+#[derive(CustomDisplay)]
+#[display("state")]
+pub enum State {
+    #[display("on")]
+    On,
+}
+
+fn main() {}
@@ -0,0 +1,86 @@
+// Large structs make for unwieldy trace logs, so the layout of the output can
+// be tuned on the type.
+//
+// With #[debug(compact)] the output stays on one line even under {:#?}, all the
+// way down through the fields. With #[debug(omit_empty)] fields holding None or
+// an empty collection are left out. With #[debug(non_exhaustive)] the output
+// ends in `..` whenever some field was left out, whether skipped or omitted.
+
+use derive_debug::CustomDebug;
+use std::collections::HashMap;
+
+#[derive(CustomDebug)]
+#[debug(compact)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(CustomDebug)]
+#[debug(compact)]
+pub struct Segment(Point, Point);
+
+#[derive(CustomDebug)]
+#[debug(non_exhaustive)]
+pub struct Connection {
+    peer: &'static str,
+    #[debug(skip)]
+    socket: u32,
+}
+
+#[derive(CustomDebug)]
+#[debug(omit_empty)]
+pub struct Request {
+    method: &'static str,
+    query: Option<&'static str>,
+    headers: HashMap<&'static str, &'static str>,
+    body: Vec<u8>,
+}
+
+#[derive(CustomDebug)]
+#[debug(omit_empty, non_exhaustive)]
+pub enum Span {
+    Entered {
+        name: String,
+        parent: Option<u64>,
+    },
+    Closed(u64, Option<u64>),
+}
+
+fn main() {
+    let segment = Segment(Point { x: 0, y: 0 }, Point { x: 1, y: 2 });
+    assert_eq!(
+        format!("{:#?}", segment),
+        "Segment(Point { x: 0, y: 0 }, Point { x: 1, y: 2 })",
+    );
+
+    let connection = Connection {
+        peer: "10.0.0.1",
+        socket: 3,
+    };
+    assert_eq!(
+        format!("{:?}", connection),
+        r#"Connection { peer: "10.0.0.1", .. }"#,
+    );
+
+    let mut request = Request {
+        method: "GET",
+        query: None,
+        headers: HashMap::new(),
+        body: Vec::new(),
+    };
+    assert_eq!(format!("{:?}", request), r#"Request { method: "GET" }"#);
+    request.query = Some("page=2");
+    request.body.push(0);
+    assert_eq!(
+        format!("{:?}", request),
+        r#"Request { method: "GET", query: Some("page=2"), body: [0] }"#,
+    );
+
+    let entered = Span::Entered {
+        name: "poll".to_owned(),
+        parent: None,
+    };
+    assert_eq!(format!("{:?}", entered), r#"Entered { name: "poll", .. }"#);
+    assert_eq!(format!("{:?}", Span::Closed(1, Some(0))), "Closed(1, Some(0))");
+}
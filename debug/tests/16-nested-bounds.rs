@@ -0,0 +1,131 @@
+// Bound inference walks the whole type of every field, so that the generated
+// impl asks for exactly what it uses:
+//
+//   - type parameters nested in containers, as in HashMap<K, Vec<V>>, are
+//     bounded themselves;
+//   - associated types, written T::Assoc or <T as Trait>::Out anywhere in the
+//     type, are bounded as a whole without bounding T;
+//   - type parameters appearing only inside trait objects or function
+//     pointers, as in Box<dyn Handler<T>> or fn(T), are not bounded at all,
+//     since those implement Debug (or not) regardless;
+//   - a field given a format string, on itself or on its variant, is bounded
+//     by the formatting traits its placeholders ask for, as in LowerHex for
+//     {:#x}, rather than by Debug.
+//
+// Where the inferred bounds are still not right, #[debug(bound = "...")] on a
+// field replaces the bounds inferred from that field alone.
+
+use derive_debug::CustomDebug;
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+pub trait Trait {
+    type Assoc;
+    type Out;
+}
+
+pub trait Handler<T>: Debug {
+    fn handle(&self, event: T);
+}
+
+#[derive(CustomDebug)]
+pub struct Registry<K, T: Trait, E> {
+    handlers: HashMap<K, Box<dyn Handler<E>>>,
+    callback: fn(E) -> bool,
+    cached: Option<Arc<T::Assoc>>,
+    output: Vec<<T as Trait>::Out>,
+}
+
+pub struct Opaque<T>(T);
+
+impl<T> Debug for Opaque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Opaque")
+    }
+}
+
+#[derive(CustomDebug)]
+pub struct Wrapper<T, U> {
+    #[debug(bound = "")]
+    opaque: Opaque<T>,
+    normal: U,
+}
+
+#[derive(CustomDebug)]
+pub struct Hex<T> {
+    #[debug = "{:#x}"]
+    value: T,
+}
+
+#[derive(CustomDebug)]
+pub enum Flags<T> {
+    #[debug = "Bits({bits:b})"]
+    Bits { bits: T },
+}
+
+// None of these implement Debug.
+pub struct NotDebug;
+
+impl Trait for NotDebug {
+    type Assoc = u8;
+    type Out = &'static str;
+}
+
+#[derive(Debug)]
+pub struct Print;
+
+impl<T> Handler<T> for Print {
+    fn handle(&self, _event: T) {}
+}
+
+// Formats in hex and binary only.
+pub struct Mask(u8);
+
+impl fmt::LowerHex for Mask {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Binary for Mask {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Binary::fmt(&self.0, f)
+    }
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    assert_debug::<Registry<u32, NotDebug, NotDebug>>();
+    assert_debug::<Wrapper<NotDebug, u8>>();
+    assert_debug::<Hex<Mask>>();
+    assert_debug::<Flags<Mask>>();
+
+    let mut handlers: HashMap<u32, Box<dyn Handler<NotDebug>>> = HashMap::new();
+    handlers.insert(1, Box::new(Print));
+    let registry = Registry::<u32, NotDebug, NotDebug> {
+        handlers,
+        callback: |_| true,
+        cached: Some(Arc::new(7)),
+        output: vec!["done"],
+    };
+    let debug = format!("{:?}", registry);
+    assert!(debug.starts_with("Registry { handlers: {1: Print}, callback: 0x"));
+    assert!(debug.ends_with(r#"cached: Some(7), output: ["done"] }"#));
+
+    let wrapper = Wrapper {
+        opaque: Opaque(NotDebug),
+        normal: 1u8,
+    };
+    assert_eq!(
+        format!("{:?}", wrapper),
+        "Wrapper { opaque: Opaque, normal: 1 }",
+    );
+
+    assert_eq!(
+        format!("{:?}", Hex { value: Mask(255) }),
+        "Hex { value: 0xff }",
+    );
+    assert_eq!(format!("{:?}", Flags::Bits { bits: Mask(5) }), "Bits(101)");
+}
@@ -0,0 +1,76 @@
+// Does the generated code still work in a crate where the names it might use
+// mean something else, or do not exist at all?
+//
+// Crates built with #![no_std] have no `std` to refer to, so the generated
+// impls must only go through `::core::fmt`. The leading `::` also keeps them
+// working when the caller has an item of their own named `core`. And as for
+// the builder, local items named `fmt`, `Result` or `Formatter` must not be
+// picked up in place of the ones from core.
+//
+// This crate is one of them, so any `::std` path in the generated code fails
+// to resolve. The standard library is only linked under another name, to run
+// `main` and format the output, and modules named `std` and `core` catch paths
+// missing the leading `::`.
+
+#![no_std]
+
+extern crate std as host;
+
+mod std {}
+mod core {}
+
+mod fmt {}
+type Result = ();
+type Formatter = ();
+type Option = ();
+type Some = ();
+
+use derive_debug::{CustomDebug, CustomDisplay};
+
+#[derive(CustomDebug)]
+#[debug(compact, omit_empty, non_exhaustive)]
+pub struct Packet<'a, T> {
+    id: u16,
+    #[debug = "0x{:04x}"]
+    flags: u16,
+    #[debug(with = "hex")]
+    payload: &'a [u8],
+    #[debug(redact)]
+    key: u32,
+    #[debug(skip)]
+    checksum: u32,
+    extra: T,
+}
+
+#[derive(CustomDebug, CustomDisplay)]
+pub enum State {
+    #[display("up for {0}s")]
+    Up(u32),
+    #[debug = "down since {since}"]
+    #[display("down")]
+    Down { since: u32 },
+}
+
+fn hex(payload: &&[u8], f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+    for byte in *payload {
+        ::core::write!(f, "{:02x}", byte)?;
+    }
+    ::core::result::Result::Ok(())
+}
+
+fn main() {
+    let packet = Packet {
+        id: 1,
+        flags: 0x80,
+        payload: &[0xca, 0xfe],
+        key: 7,
+        checksum: 0,
+        extra: "",
+    };
+    assert_eq!(
+        host::format!("{:#?}", packet),
+        r#"Packet { id: 1, flags: 0x0080, payload: cafe, key: [REDACTED], extra: "", .. }"#,
+    );
+    assert_eq!(host::format!("{}", State::Up(3)), "up for 3s");
+    assert_eq!(host::format!("{:?}", State::Down { since: 9 }), "down since 9");
+}
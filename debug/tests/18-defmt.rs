@@ -0,0 +1,58 @@
+// With the `defmt` feature of derive_debug, a CustomDebug type marked
+// #[debug(defmt)] also gets a defmt::Format impl, for logging from
+// microcontrollers. It follows the same attributes as Debug: format strings
+// turn into display hints, skipped fields are left out and redacted ones print
+// their placeholder.
+//
+// The impl is opt-in per type since many types, say with a String field, have
+// a Debug impl but could not have a Format one.
+//
+// The output itself is encoded for the host side of defmt to decode, so this
+// only checks that the impls exist and carry the right bounds.
+
+use derive_debug::CustomDebug;
+use std::fmt;
+
+#[derive(CustomDebug)]
+#[debug(defmt)]
+pub struct Reading<T> {
+    sensor: &'static str,
+    #[debug = "0x{:02x}"]
+    register: u8,
+    value: T,
+    #[debug(redact)]
+    key: [u8; 4],
+    #[debug(skip)]
+    raw: Vec<u8>,
+}
+
+#[derive(CustomDebug)]
+#[debug(defmt)]
+pub enum Event {
+    Boot,
+    #[debug = "fault {code}"]
+    Fault { code: u16 },
+    Sample(#[debug(with = "celsius")] i32, u32),
+}
+
+fn celsius(value: &i32, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}°C", value)
+}
+
+// Does not implement Format.
+pub struct NotFormat;
+
+#[derive(CustomDebug)]
+#[debug(defmt)]
+pub struct Wrapper<T> {
+    #[debug(skip)]
+    inner: T,
+}
+
+fn assert_format<F: defmt::Format>() {}
+
+fn main() {
+    assert_format::<Reading<u32>>();
+    assert_format::<Event>();
+    assert_format::<Wrapper<NotFormat>>();
+}
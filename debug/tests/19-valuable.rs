@@ -0,0 +1,102 @@
+// With the `valuable` feature of derive_debug, a CustomDebug type marked
+// #[debug(valuable)] also implements valuable::Valuable, through which tracing
+// and structured loggers receive each field as a typed key/value pair rather
+// than one formatted string.
+//
+// Skipped fields are not visited and redacted ones are visited as their
+// placeholder string. Format strings keep applying to the Debug output only.
+
+use derive_debug::CustomDebug;
+use valuable::{NamedValues, Valuable, Value, Visit};
+
+#[derive(CustomDebug)]
+#[debug(valuable)]
+pub struct Request<T> {
+    method: &'static str,
+    #[debug = "{}ms"]
+    latency: u64,
+    #[debug(redact)]
+    token: T,
+    #[debug(skip)]
+    buffer: Vec<u8>,
+    peer: Peer,
+}
+
+#[derive(CustomDebug)]
+#[debug(valuable)]
+pub enum Peer {
+    Local,
+    Remote(&'static str, u16),
+}
+
+/// Flattens what gets visited into `key=value` pairs.
+struct Collect(Vec<String>);
+
+impl Visit for Collect {
+    fn visit_value(&mut self, value: Value<'_>) {
+        match value {
+            Value::Structable(v) => {
+                self.0.push(format!("struct={}", v.definition().name()));
+                v.visit(self);
+            }
+            Value::Enumerable(v) => {
+                self.0.push(format!("variant={}", v.variant().name()));
+                v.visit(self);
+            }
+            Value::String(v) => self.0.push(format!("{:?}", v)),
+            Value::U16(v) => self.0.push(v.to_string()),
+            Value::U64(v) => self.0.push(v.to_string()),
+            value => self.0.push(format!("{:?}", value)),
+        }
+    }
+
+    fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+        for (field, value) in named_values {
+            self.0.push(format!("{}=", field.name()));
+            self.visit_value(*value);
+        }
+    }
+
+    fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+        for value in values {
+            self.visit_value(*value);
+        }
+    }
+}
+
+fn main() {
+    let request = Request {
+        method: "GET",
+        latency: 12,
+        token: "secret",
+        buffer: Vec::new(),
+        peer: Peer::Remote("10.0.0.1", 443),
+    };
+    assert_eq!(
+        format!("{:?}", request),
+        r#"Request { method: "GET", latency: 12ms, token: [REDACTED], peer: Remote("10.0.0.1", 443) }"#,
+    );
+
+    let mut collect = Collect(Vec::new());
+    collect.visit_value(request.as_value());
+    assert_eq!(
+        collect.0,
+        [
+            "struct=Request",
+            "method=",
+            "\"GET\"",
+            "latency=",
+            "12",
+            "token=",
+            "\"[REDACTED]\"",
+            "peer=",
+            "variant=Remote",
+            "\"10.0.0.1\"",
+            "443",
+        ],
+    );
+
+    let mut collect = Collect(Vec::new());
+    collect.visit_value(Peer::Local.as_value());
+    assert_eq!(collect.0, ["variant=Local"]);
+}
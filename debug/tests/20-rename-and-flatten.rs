@@ -0,0 +1,67 @@
+// Log readers often expect the field names of some wire format rather than
+// those of the Rust struct.
+//
+// #[debug(rename = "...")] prints a field under another name, and
+// #[debug(rename_all = "...")] on the type converts every field name to one of
+// the usual case conventions: "camelCase", "PascalCase", "kebab-case",
+// "SCREAMING_SNAKE_CASE" and so on. An explicit rename wins over rename_all.
+//
+// #[debug(flatten)] prints the fields of a nested struct, itself deriving
+// CustomDebug, as if they were fields of the outer one. The nested struct's own
+// attributes still apply to its fields. Since that goes through a hidden method
+// of the nested struct, the struct has to opt in with #[debug(flattenable)].
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(rename_all = "camelCase", flattenable)]
+pub struct Session {
+    user_id: u32,
+    #[debug(rename = "ip")]
+    remote_addr: &'static str,
+    #[debug(flatten)]
+    timing: Timing,
+    r#type: &'static str,
+}
+
+#[derive(CustomDebug)]
+#[debug(rename_all = "SCREAMING_SNAKE_CASE", omit_empty, flattenable)]
+pub struct Timing {
+    started_at: u64,
+    #[debug = "{}ms"]
+    time_to_first_byte: u64,
+    ended_at: Option<u64>,
+}
+
+#[derive(CustomDebug)]
+pub enum Event {
+    Login {
+        #[debug(flatten)]
+        session: Session,
+    },
+    Logout,
+}
+
+fn main() {
+    let session = Session {
+        user_id: 7,
+        remote_addr: "10.0.0.1",
+        timing: Timing {
+            started_at: 100,
+            time_to_first_byte: 12,
+            ended_at: None,
+        },
+        r#type: "web",
+    };
+    assert_eq!(
+        format!("{:?}", session),
+        r#"Session { userId: 7, ip: "10.0.0.1", STARTED_AT: 100, TIME_TO_FIRST_BYTE: 12ms, type: "web" }"#,
+    );
+
+    let event = Event::Login { session };
+    assert_eq!(
+        format!("{:?}", event),
+        r#"Login { userId: 7, ip: "10.0.0.1", STARTED_AT: 100, TIME_TO_FIRST_BYTE: 12ms, type: "web" }"#,
+    );
+    assert_eq!(format!("{:?}", Event::Logout), "Logout");
+}
@@ -0,0 +1,44 @@
+// Format strings on fields are checked when the derive expands, and mistakes
+// are reported at the string instead of somewhere in the generated code.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct UnknownTrait {
+    #[debug = "{:q}"]
+    id: u32,
+}
+
+#[derive(CustomDebug)]
+pub struct TwoPlaceholders {
+    #[debug = "{} of {}"]
+    page: u32,
+}
+
+#[derive(CustomDebug)]
+pub struct NoPlaceholder {
+    #[debug = "hidden"]
+    secret: u32,
+}
+
+#[derive(CustomDebug)]
+pub struct NamedArgument {
+    #[debug = "{:>width$}"]
+    label: &'static str,
+}
+
+#[derive(CustomDebug)]
+pub struct Unterminated {
+    #[debug = "0x{:08x"]
+    addr: u32,
+}
+
+#[derive(CustomDebug)]
+pub struct Fine {
+    #[debug = "{0:#06x} ({0})"]
+    flags: u16,
+    #[debug = "{:>10.3}"]
+    ratio: f32,
+}
+
+fn main() {}
@@ -0,0 +1,46 @@
+// Format strings on variants may only refer to fields of that variant, and a
+// tuple variant's fields have to be used, the same as arguments of `write!`.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub enum UnknownField {
+    #[debug = "{status} after {millis}ms"]
+    Done { status: u16, elapsed: u64 },
+}
+
+#[derive(CustomDebug)]
+pub enum UnusedField {
+    #[debug = "failed: {0:?}"]
+    Failed(&'static str, u32),
+}
+
+#[derive(CustomDebug)]
+pub enum PositionalInNamed {
+    #[debug = "pending since {}"]
+    Pending { since: u64 },
+}
+
+#[derive(CustomDebug)]
+pub enum MissingPrecision {
+    #[debug = "retry #{:.}"]
+    Retry(u32),
+}
+
+#[derive(CustomDebug)]
+pub enum UnitWithPlaceholder {
+    #[debug = "closed {0}"]
+    Closed,
+}
+
+#[derive(CustomDebug)]
+pub enum Fine {
+    #[debug = "{status} after {elapsed:.3}ms"]
+    Done { status: u16, elapsed: f64 },
+    #[debug = "{1}: {0:?}"]
+    Failed(&'static str, u32),
+    #[debug = "closed"]
+    Closed,
+}
+
+fn main() {}
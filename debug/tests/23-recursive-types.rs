@@ -0,0 +1,83 @@
+// Trees with parent pointers and deeply nested lists would overflow the stack,
+// or print forever, under {:?}.
+//
+// With #[debug(max_depth = N)] the type prints at most N levels of itself and
+// `…` below that. With #[debug(detect_cycles)] a value reached again while it is
+// still being printed, as through an Rc back to its parent, prints `<cycle>`.
+// Both keep count in thread-locals, so they need std.
+
+use derive_debug::CustomDebug;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(CustomDebug)]
+#[debug(max_depth = 3)]
+pub struct List {
+    value: u32,
+    next: Option<Box<List>>,
+}
+
+#[derive(CustomDebug)]
+#[debug(detect_cycles)]
+pub struct Node {
+    name: &'static str,
+    parent: Option<Rc<RefCell<Node>>>,
+    children: Vec<Rc<RefCell<Node>>>,
+}
+
+fn main() {
+    let mut list = List {
+        value: 0,
+        next: None,
+    };
+    for value in 1..5 {
+        list = List {
+            value,
+            next: Some(Box::new(list)),
+        };
+    }
+    assert_eq!(
+        format!("{:?}", list),
+        "List { value: 4, next: Some(List { value: 3, next: Some(List { value: 2, next: Some(…) }) }) }",
+    );
+    // The count is back to zero afterwards.
+    assert_eq!(format!("{:?}", list), format!("{:?}", list));
+
+    let root = Rc::new(RefCell::new(Node {
+        name: "root",
+        parent: None,
+        children: Vec::new(),
+    }));
+    let leaf = Rc::new(RefCell::new(Node {
+        name: "leaf",
+        parent: Some(Rc::clone(&root)),
+        children: Vec::new(),
+    }));
+    root.borrow_mut().children.push(Rc::clone(&leaf));
+    assert_eq!(
+        format!("{:?}", root.borrow()),
+        "Node { name: \"root\", parent: None, children: [RefCell { value: Node { \
+         name: \"leaf\", parent: Some(RefCell { value: <cycle> }), children: [] } }] }",
+    );
+
+    // Siblings are not cycles.
+    let shared = Rc::new(RefCell::new(Node {
+        name: "shared",
+        parent: None,
+        children: Vec::new(),
+    }));
+    let twice = Node {
+        name: "twice",
+        parent: None,
+        children: vec![Rc::clone(&shared), shared],
+    };
+    assert_eq!(
+        format!("{:?}", twice),
+        "Node { name: \"twice\", parent: None, children: [RefCell { value: Node { \
+         name: \"shared\", parent: None, children: [] } }, RefCell { value: Node { \
+         name: \"shared\", parent: None, children: [] } }] }",
+    );
+
+    // Break the cycle so the nodes are dropped.
+    root.borrow_mut().children.clear();
+}
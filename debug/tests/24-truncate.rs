@@ -0,0 +1,60 @@
+// Fields holding large payloads flood the logs when printed in full.
+//
+// With #[debug(truncate = N)] a field prints at most N of the items it iterates
+// over, followed by the number of those left out. Fields spelled as a string
+// are cut after N characters instead. Anything else a reference to which
+// iterates, such as a map or a boxed slice, prints as a list.
+
+use derive_debug::CustomDebug;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+#[derive(CustomDebug)]
+pub struct Packet<'a> {
+    id: u32,
+    #[debug(truncate = 4)]
+    body: Vec<u8>,
+    #[debug(truncate = 5)]
+    summary: Cow<'a, str>,
+    #[debug(truncate = 1)]
+    headers: BTreeMap<&'a str, &'a str>,
+}
+
+#[derive(CustomDebug)]
+pub enum Message {
+    Text(#[debug(truncate = 3)] String),
+    Binary(#[debug(truncate = 2)] Box<[u8]>),
+}
+
+fn main() {
+    let mut headers = BTreeMap::new();
+    headers.insert("host", "example.com");
+    headers.insert("accept", "*/*");
+    let packet = Packet {
+        id: 7,
+        body: vec![0; 1000],
+        summary: Cow::Borrowed("héllo wörld"),
+        headers,
+    };
+    assert_eq!(
+        format!("{:?}", packet),
+        "Packet { id: 7, body: [0, 0, 0, 0, ... (996 more)], summary: \"héllo\"... (6 more), \
+         headers: [(\"accept\", \"*/*\"), ... (1 more)] }",
+    );
+
+    // Short enough values print as usual, strings with their quotes.
+    let text = Message::Text("abc".to_owned());
+    assert_eq!(format!("{:?}", text), "Text(\"abc\")");
+    let binary = Message::Binary(Box::new([1, 2]));
+    assert_eq!(format!("{:?}", binary), "Binary([1, 2])");
+
+    let binary = Message::Binary(Box::new([1, 2, 3]));
+    let expected = r#"Binary(
+    [
+        1,
+        2,
+        ... (1 more),
+    ],
+)"#;
+    assert_eq!(format!("{:#?}", binary), expected);
+}
@@ -0,0 +1,71 @@
+// With the `ufmt` feature of derive_debug, a CustomDebug type marked
+// #[debug(ufmt)] also implements ufmt::uDebug, for printing from
+// microcontrollers without pulling in core::fmt. It follows the same attributes
+// as Debug and prints the same single-line output, with format strings written
+// through uwrite!.
+//
+// ufmt has no uDebug impl for str, so a string field is printed through its
+// uDisplay impl by a format string instead.
+
+use derive_debug::CustomDebug;
+use std::convert::Infallible;
+use ufmt::{uWrite, uwrite};
+
+#[derive(CustomDebug)]
+#[debug(ufmt)]
+pub struct Reading<T> {
+    #[debug = "\"{}\""]
+    sensor: &'static str,
+    #[debug = "{:#04x}"]
+    register: u8,
+    #[debug = "{}%"]
+    level: u8,
+    value: T,
+    #[debug(redact)]
+    key: [u8; 4],
+    #[debug(skip)]
+    raw: Vec<u8>,
+}
+
+#[derive(CustomDebug)]
+#[debug(ufmt)]
+pub enum Event {
+    Boot,
+    #[debug = "fault {code:X}"]
+    Fault { code: u16 },
+    Sample(u32, i16),
+}
+
+struct Buffer(String);
+
+impl uWrite for Buffer {
+    type Error = Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Infallible> {
+        self.0.push_str(s);
+        Ok(())
+    }
+}
+
+fn main() {
+    let reading = Reading {
+        sensor: "t0",
+        register: 0x2a,
+        level: 80,
+        value: -5i32,
+        key: [1, 2, 3, 4],
+        raw: vec![0],
+    };
+
+    let mut buffer = Buffer(String::new());
+    uwrite!(buffer, "{:?}", reading).unwrap();
+    assert_eq!(
+        buffer.0,
+        "Reading { sensor: \"t0\", register: 0x2a, level: 80%, value: -5, key: [REDACTED] }",
+    );
+    assert_eq!(buffer.0, format!("{:?}", reading));
+
+    let mut buffer = Buffer(String::new());
+    uwrite!(buffer, "{:?} {:?} {:?}", Event::Boot, Event::Fault { code: 0xbeef }, Event::Sample(3, -1)).unwrap();
+    assert_eq!(buffer.0, "Boot fault BEEF Sample(3, -1)");
+}
@@ -0,0 +1,21 @@
+// uwrite! only understands `{}`, `{:?}`, `{:#?}` and hexadecimal, so a format
+// string meant for ufmt that asks for anything else is reported at the string
+// itself rather than from within the generated impl.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(ufmt)]
+pub struct Flags {
+    #[debug = "{:08b}"]
+    bits: u8,
+}
+
+#[derive(CustomDebug)]
+#[debug(ufmt)]
+pub enum Reading {
+    #[debug = "{0:.2} V"]
+    Voltage(f32),
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+// Only a struct with named fields has fields another struct could print as
+// its own, so #[debug(flattenable)] on anything else is an error. A field can
+// only be flattened if its type is #[debug(flattenable)], which the error at
+// the field type says otherwise.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(flattenable)]
+pub enum State {
+    Idle,
+    Busy { since: u64 },
+}
+
+#[derive(CustomDebug)]
+#[debug(flattenable)]
+pub struct Pair(u32, u32);
+
+#[derive(CustomDebug)]
+pub struct Limits {
+    min: u32,
+    max: u32,
+}
+
+#[derive(CustomDebug)]
+pub struct Config {
+    name: String,
+    #[debug(flatten)]
+    limits: Limits,
+}
+
+fn main() {}
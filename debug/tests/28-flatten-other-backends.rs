@@ -0,0 +1,35 @@
+// The fields of a flattened struct are added through a hidden method writing to
+// a core::fmt::DebugStruct, which the defmt, ufmt and valuable impls have no
+// counterpart of. Rather than printing the nested struct differently from
+// Debug, #[debug(flatten)] is rejected when any of them is derived.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(flattenable)]
+pub struct Timing {
+    started_at: u64,
+}
+
+#[derive(CustomDebug)]
+#[debug(defmt)]
+pub struct Frame {
+    #[debug(flatten)]
+    timing: Timing,
+}
+
+#[derive(CustomDebug)]
+#[debug(ufmt)]
+pub struct Reading {
+    #[debug(flatten)]
+    timing: Timing,
+}
+
+#[derive(CustomDebug)]
+#[debug(valuable)]
+pub struct Request {
+    #[debug(flatten)]
+    timing: Timing,
+}
+
+fn main() {}
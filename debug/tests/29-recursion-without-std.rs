@@ -0,0 +1,20 @@
+// max_depth and detect_cycles keep track of the values being printed in
+// thread-locals, which only std provides. Built without its `std` feature, as
+// for a no_std crate, derive_debug reports them at the attribute instead of
+// generating code referring to std.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(max_depth = 4)]
+pub struct Node {
+    children: &'static [Node],
+}
+
+#[derive(CustomDebug)]
+#[debug(detect_cycles)]
+pub struct Link {
+    next: Option<&'static Link>,
+}
+
+fn main() {}
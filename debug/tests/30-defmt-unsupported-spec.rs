@@ -0,0 +1,24 @@
+// defmt::write! takes display hints rather than Rust format specs, and only
+// `?`, `x`, `X`, `b` and `o`, with `#` and zero padding, mean the same in
+// both. Anything else in a format string meant for defmt is reported at the
+// string itself rather than from within the generated impl.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(defmt)]
+pub struct Reading {
+    #[debug = "{:.2}"]
+    volts: f32,
+    #[debug = "{:>8}"]
+    label: &'static str,
+}
+
+#[derive(CustomDebug)]
+#[debug(defmt)]
+pub enum Event {
+    #[debug = "code {0:e}"]
+    Fault(f32),
+}
+
+fn main() {}
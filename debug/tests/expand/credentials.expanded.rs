@@ -0,0 +1,22 @@
+impl<T, U> ::core::fmt::Debug for Credentials<T, U> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self { user, password: _, token: _, salt: _ } => {
+                f
+                    .debug_struct("Credentials")
+                    .field("user", user)
+                    .field("password", &::core::format_args!("{}", "[REDACTED]"))
+                    .field("token", &::core::format_args!("{}", "***"))
+                    .finish()
+            }
+        }
+    }
+}
+
+impl ::core::fmt::Debug for Hashed {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self(_0, _) => f.debug_tuple("Hashed").field(_0).finish(),
+        }
+    }
+}
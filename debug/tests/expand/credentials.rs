@@ -0,0 +1,15 @@
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Credentials<T, U> {
+    user: &'static str,
+    #[debug(redact)]
+    password: T,
+    #[debug(redact = "***")]
+    token: U,
+    #[debug(skip)]
+    salt: u64,
+}
+
+#[derive(CustomDebug)]
+pub struct Hashed(&'static str, #[debug(skip)] [u8; 4]);
@@ -0,0 +1,67 @@
+impl<T> ::core::fmt::Debug for Frame<T>
+where
+    T: ::core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        struct DebugTruncated<'a, T: ?Sized>(&'a T, usize);
+        impl<'a, T: ?Sized> ::core::fmt::Debug for DebugTruncated<'a, T>
+        where
+            &'a T: ::core::iter::IntoIterator,
+            <&'a T as ::core::iter::IntoIterator>::Item: ::core::fmt::Debug,
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut list = f.debug_list();
+                let mut items = ::core::iter::IntoIterator::into_iter(self.0);
+                let shown = ::core::iter::Iterator::by_ref(&mut items);
+                list.entries(::core::iter::Iterator::take(shown, self.1));
+                let more = ::core::iter::Iterator::count(items);
+                if more > 0 {
+                    list.entry(&::core::format_args!("... ({} more)", more));
+                }
+                list.finish()
+            }
+        }
+        match self {
+            Self { channel, samples } => {
+                f
+                    .debug_struct("Frame")
+                    .field("channel", channel)
+                    .field("samples", &DebugTruncated(samples, 8))
+                    .finish()
+            }
+        }
+    }
+}
+impl<T> ::defmt::Format for Frame<T>
+where
+    T: ::core::fmt::Debug,
+{
+    fn format(&self, f: ::defmt::Formatter<'_>) {
+        struct DebugTruncated<'a, T: ?Sized>(&'a T, usize);
+        impl<'a, T: ?Sized> ::core::fmt::Debug for DebugTruncated<'a, T>
+        where
+            &'a T: ::core::iter::IntoIterator,
+            <&'a T as ::core::iter::IntoIterator>::Item: ::core::fmt::Debug,
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut list = f.debug_list();
+                let mut items = ::core::iter::IntoIterator::into_iter(self.0);
+                let shown = ::core::iter::Iterator::by_ref(&mut items);
+                list.entries(::core::iter::Iterator::take(shown, self.1));
+                let more = ::core::iter::Iterator::count(items);
+                if more > 0 {
+                    list.entry(&::core::format_args!("... ({} more)", more));
+                }
+                list.finish()
+            }
+        }
+        match self {
+            Self { channel, samples } => {
+                ::defmt::write!(
+                    f, "Frame {{ channel: {}, samples: {} }}", channel,
+                    ::defmt::Debug2Format(& DebugTruncated(samples, 8))
+                )
+            }
+        }
+    }
+}
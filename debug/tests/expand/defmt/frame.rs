@@ -0,0 +1,9 @@
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(defmt)]
+pub struct Frame<T> {
+    channel: u8,
+    #[debug(truncate = 8)]
+    samples: Vec<T>,
+}
@@ -0,0 +1,79 @@
+impl<T> ::core::fmt::Debug for Reading<T>
+where
+    T: ::core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self { sensor, register, value, key: _, raw: _ } => {
+                f
+                    .debug_struct("Reading")
+                    .field("sensor", sensor)
+                    .field("register", &::core::format_args!("0x{:02x}", register))
+                    .field("value", value)
+                    .field("key", &::core::format_args!("{}", "[REDACTED]"))
+                    .finish_non_exhaustive()
+            }
+        }
+    }
+}
+impl<T> ::defmt::Format for Reading<T>
+where
+    T: ::defmt::Format,
+{
+    fn format(&self, f: ::defmt::Formatter<'_>) {
+        match self {
+            Self { sensor, register, value, key: _, raw: _ } => {
+                ::defmt::write!(
+                    f,
+                    "Reading {{ sensor: {}, register: 0x{:02x}, value: {}, key: [REDACTED], .. }}",
+                    sensor, register, value
+                )
+            }
+        }
+    }
+}
+
+impl ::core::fmt::Debug for Event {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        struct DebugWith<'a, T: ?Sized>(
+            &'a T,
+            fn(&T, &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result,
+        );
+        impl<T: ?Sized> ::core::fmt::Debug for DebugWith<'_, T> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                (self.1)(self.0, f)
+            }
+        }
+        match self {
+            Self::Boot => f.write_str("Boot"),
+            #[allow(unused_variables)]
+            Self::Fault { code, at } => ::core::write!(f, "fault {code}"),
+            Self::Sample(_0, _1) => {
+                f.debug_tuple("Sample").field(&DebugWith(_0, celsius)).field(_1).finish()
+            }
+        }
+    }
+}
+impl ::defmt::Format for Event {
+    fn format(&self, f: ::defmt::Formatter<'_>) {
+        struct DebugWith<'a, T: ?Sized>(
+            &'a T,
+            fn(&T, &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result,
+        );
+        impl<T: ?Sized> ::core::fmt::Debug for DebugWith<'_, T> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                (self.1)(self.0, f)
+            }
+        }
+        match self {
+            Self::Boot => ::defmt::write!(f, "Boot"),
+            Self::Fault { code, .. } => ::defmt::write!(f, "fault {}", code),
+            Self::Sample(_0, _1) => {
+                ::defmt::write!(
+                    f, "Sample({}, {})", ::defmt::Debug2Format(& DebugWith(_0, celsius)),
+                    _1
+                )
+            }
+        }
+    }
+}
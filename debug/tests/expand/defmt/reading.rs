@@ -0,0 +1,23 @@
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(defmt, non_exhaustive)]
+pub struct Reading<T> {
+    sensor: &'static str,
+    #[debug = "0x{:02x}"]
+    register: u8,
+    value: T,
+    #[debug(redact)]
+    key: [u8; 4],
+    #[debug(skip)]
+    raw: Vec<u8>,
+}
+
+#[derive(CustomDebug)]
+#[debug(defmt)]
+pub enum Event {
+    Boot,
+    #[debug = "fault {code}"]
+    Fault { code: u16, at: u64 },
+    Sample(#[debug(with = "celsius")] i32, u32),
+}
@@ -0,0 +1,25 @@
+impl ::core::fmt::Display for User {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self { id: _0, name: _1, .. } => {
+                ::core::write!(f, "{_1} ({_0})", _0 = _0, _1 = _1)
+            }
+        }
+    }
+}
+
+impl<T> ::core::fmt::Display for Event<T>
+where
+    T: ::core::fmt::Debug,
+    T: ::core::fmt::Display,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self::Login { user: _0, .. } => ::core::write!(f, "{_0} logged in", _0 = _0),
+            Self::Leave(_0, _1) => {
+                ::core::write!(f, "{_0:?} left: {_1}", _0 = _0, _1 = _1)
+            }
+            Self::Shutdown { .. } => f.write_str("Shutdown"),
+        }
+    }
+}
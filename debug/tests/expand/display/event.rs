@@ -0,0 +1,18 @@
+use derive_debug::CustomDisplay;
+
+#[derive(CustomDisplay)]
+#[display("{name} ({id})")]
+pub struct User {
+    id: u32,
+    name: &'static str,
+    email: &'static str,
+}
+
+#[derive(CustomDisplay)]
+pub enum Event<T> {
+    #[display("{user} logged in")]
+    Login { user: T },
+    #[display("{0:?} left: {1}")]
+    Leave(T, &'static str),
+    Shutdown,
+}
@@ -0,0 +1,49 @@
+impl<E> ::core::fmt::Debug for Error<E>
+where
+    E: ::core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self::Io { path, code } => {
+                f
+                    .debug_struct("Io")
+                    .field("path", path)
+                    .field("code", &::core::format_args!("{:#x}", code))
+                    .finish()
+            }
+            Self::Parse(_0, _1) => {
+                f
+                    .debug_tuple("Parse")
+                    .field(_0)
+                    .field(&::core::format_args!("line {}", _1))
+                    .finish()
+            }
+            Self::Other(_0) => f.debug_tuple("Other").field(_0).finish(),
+            #[allow(unused_variables)]
+            Self::Timeout { secs } => ::core::write!(f, "timed out after {secs}s"),
+            Self::Unknown => f.write_str("Unknown"),
+        }
+    }
+}
+
+impl ::core::fmt::Debug for Bitmask {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self(_0, _1) => {
+                f
+                    .debug_tuple("Bitmask")
+                    .field(_0)
+                    .field(&::core::format_args!("0b{:08b}", _1))
+                    .finish()
+            }
+        }
+    }
+}
+
+impl ::core::fmt::Debug for Unit {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self => f.write_str("Unit"),
+        }
+    }
+}
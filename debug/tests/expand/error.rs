@@ -0,0 +1,21 @@
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub enum Error<E> {
+    Io {
+        path: &'static str,
+        #[debug = "{:#x}"]
+        code: u32,
+    },
+    Parse(usize, #[debug = "line {}"] usize),
+    Other(E),
+    #[debug = "timed out after {secs}s"]
+    Timeout { secs: u64 },
+    Unknown,
+}
+
+#[derive(CustomDebug)]
+pub struct Bitmask(&'static str, #[debug = "0b{:08b}"] u8);
+
+#[derive(CustomDebug)]
+pub struct Unit;
@@ -0,0 +1,31 @@
+impl<T, U: Trait> ::core::fmt::Debug for Field<T, U>
+where
+    T: ::core::fmt::Debug,
+    U::Value: ::core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self { name, bitmask, value, values, marker } => {
+                f
+                    .debug_struct("Field")
+                    .field("name", name)
+                    .field("bitmask", &::core::format_args!("0b{:08b}", bitmask))
+                    .field("value", value)
+                    .field("values", values)
+                    .field("marker", marker)
+                    .finish()
+            }
+        }
+    }
+}
+
+impl<T: Trait> ::core::fmt::Debug for Wrapper<T>
+where
+    T::Value: Debug,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self { field } => f.debug_struct("Wrapper").field("field", field).finish(),
+        }
+    }
+}
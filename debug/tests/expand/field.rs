@@ -0,0 +1,18 @@
+use derive_debug::CustomDebug;
+use std::marker::PhantomData;
+
+#[derive(CustomDebug)]
+pub struct Field<T, U: Trait> {
+    name: &'static str,
+    #[debug = "0b{:08b}"]
+    bitmask: u8,
+    value: T,
+    values: Vec<U::Value>,
+    marker: PhantomData<U>,
+}
+
+#[derive(CustomDebug)]
+#[debug(bound = "T::Value: Debug")]
+pub struct Wrapper<T: Trait> {
+    field: Field<u8, T>,
+}
@@ -0,0 +1,29 @@
+impl ::core::fmt::Debug for Request {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        if f.alternate() {
+            return ::core::write!(f, "{:?}", self);
+        }
+        match self {
+            Self { method, query, body, socket: _ } => {
+                let builder = &mut f.debug_struct("Request");
+                let mut omitted = true;
+                if method.is_empty() {
+                    omitted = true;
+                } else {
+                    builder.field("method", method);
+                }
+                if query.is_none() {
+                    omitted = true;
+                } else {
+                    builder.field("query", query);
+                }
+                if body.is_empty() {
+                    omitted = true;
+                } else {
+                    builder.field("body", body);
+                }
+                if omitted { builder.finish_non_exhaustive() } else { builder.finish() }
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(compact, omit_empty, non_exhaustive)]
+pub struct Request {
+    method: &'static str,
+    query: Option<String>,
+    body: Vec<u8>,
+    #[debug(skip)]
+    socket: u32,
+}
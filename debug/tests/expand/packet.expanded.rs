@@ -0,0 +1,22 @@
+impl<T> ::core::fmt::Debug for Packet<T> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        struct DebugWith<'a, T: ?Sized>(
+            &'a T,
+            fn(&T, &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result,
+        );
+        impl<T: ?Sized> ::core::fmt::Debug for DebugWith<'_, T> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                (self.1)(self.0, f)
+            }
+        }
+        match self {
+            Self { payload, context } => {
+                f
+                    .debug_struct("Packet")
+                    .field("payload", &DebugWith(payload, fmt_helpers::hex))
+                    .field("context", &DebugWith(context, opaque))
+                    .finish()
+            }
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Packet<T> {
+    #[debug(with = "fmt_helpers::hex")]
+    payload: Vec<u8>,
+    #[debug(with = "opaque")]
+    context: T,
+}
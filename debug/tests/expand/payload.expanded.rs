@@ -0,0 +1,101 @@
+impl<'a> ::core::fmt::Debug for Packet<'a> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        struct DebugTruncated<'a, T: ?Sized>(&'a T, usize);
+        impl<'a, T: ?Sized> ::core::fmt::Debug for DebugTruncated<'a, T>
+        where
+            &'a T: ::core::iter::IntoIterator,
+            <&'a T as ::core::iter::IntoIterator>::Item: ::core::fmt::Debug,
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut list = f.debug_list();
+                let mut items = ::core::iter::IntoIterator::into_iter(self.0);
+                let shown = ::core::iter::Iterator::by_ref(&mut items);
+                list.entries(::core::iter::Iterator::take(shown, self.1));
+                let more = ::core::iter::Iterator::count(items);
+                if more > 0 {
+                    list.entry(&::core::format_args!("... ({} more)", more));
+                }
+                list.finish()
+            }
+        }
+        struct DebugTruncatedStr<'a>(&'a str, usize);
+        impl ::core::fmt::Debug for DebugTruncatedStr<'_> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match ::core::iter::Iterator::nth(&mut self.0.char_indices(), self.1) {
+                    ::core::option::Option::Some((end, _)) => {
+                        ::core::fmt::Debug::fmt(&self.0[..end], f)?;
+                        let more = ::core::iter::Iterator::count(self.0[end..].chars());
+                        ::core::write!(f, "... ({} more)", more)
+                    }
+                    ::core::option::Option::None => ::core::fmt::Debug::fmt(self.0, f),
+                }
+            }
+        }
+        match self {
+            Self { id, body, summary, headers } => {
+                f
+                    .debug_struct("Packet")
+                    .field("id", id)
+                    .field("body", &DebugTruncated(body, 16))
+                    .field(
+                        "summary",
+                        &DebugTruncatedStr(
+                            ::core::convert::AsRef::<str>::as_ref(summary),
+                            40,
+                        ),
+                    )
+                    .field("headers", &DebugTruncated(headers, 4))
+                    .finish()
+            }
+        }
+    }
+}
+
+impl ::core::fmt::Debug for Message {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        struct DebugTruncatedStr<'a>(&'a str, usize);
+        impl ::core::fmt::Debug for DebugTruncatedStr<'_> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match ::core::iter::Iterator::nth(&mut self.0.char_indices(), self.1) {
+                    ::core::option::Option::Some((end, _)) => {
+                        ::core::fmt::Debug::fmt(&self.0[..end], f)?;
+                        let more = ::core::iter::Iterator::count(self.0[end..].chars());
+                        ::core::write!(f, "... ({} more)", more)
+                    }
+                    ::core::option::Option::None => ::core::fmt::Debug::fmt(self.0, f),
+                }
+            }
+        }
+        struct DebugTruncated<'a, T: ?Sized>(&'a T, usize);
+        impl<'a, T: ?Sized> ::core::fmt::Debug for DebugTruncated<'a, T>
+        where
+            &'a T: ::core::iter::IntoIterator,
+            <&'a T as ::core::iter::IntoIterator>::Item: ::core::fmt::Debug,
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut list = f.debug_list();
+                let mut items = ::core::iter::IntoIterator::into_iter(self.0);
+                let shown = ::core::iter::Iterator::by_ref(&mut items);
+                list.entries(::core::iter::Iterator::take(shown, self.1));
+                let more = ::core::iter::Iterator::count(items);
+                if more > 0 {
+                    list.entry(&::core::format_args!("... ({} more)", more));
+                }
+                list.finish()
+            }
+        }
+        match self {
+            Self::Text(_0) => {
+                f
+                    .debug_tuple("Text")
+                    .field(
+                        &DebugTruncatedStr(::core::convert::AsRef::<str>::as_ref(_0), 80),
+                    )
+                    .finish()
+            }
+            Self::Binary(_0) => {
+                f.debug_tuple("Binary").field(&DebugTruncated(_0, 16)).finish()
+            }
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use derive_debug::CustomDebug;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+#[derive(CustomDebug)]
+pub struct Packet<'a> {
+    id: u32,
+    #[debug(truncate = 16)]
+    body: Vec<u8>,
+    #[debug(truncate = 40)]
+    summary: Cow<'a, str>,
+    #[debug(truncate = 4)]
+    headers: BTreeMap<&'a str, &'a str>,
+}
+
+#[derive(CustomDebug)]
+pub enum Message {
+    Text(#[debug(truncate = 80)] String),
+    Binary(#[debug(truncate = 16)] Box<[u8]>),
+}
@@ -0,0 +1,24 @@
+impl<K, T: Trait, E, U> ::core::fmt::Debug for Registry<K, T, E, U>
+where
+    K: ::core::fmt::Debug,
+    T::Assoc: ::core::fmt::Debug,
+    <T as Trait>::Out: ::core::fmt::Debug,
+    <T::Assoc as Trait>::Out: ::core::fmt::Debug,
+    Opaque<U>: Debug,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self { handlers, callback, cached, output, nested, opaque } => {
+                f
+                    .debug_struct("Registry")
+                    .field("handlers", handlers)
+                    .field("callback", callback)
+                    .field("cached", cached)
+                    .field("output", output)
+                    .field("nested", nested)
+                    .field("opaque", opaque)
+                    .finish()
+            }
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Registry<K, T: Trait, E, U> {
+    handlers: HashMap<K, Box<dyn Handler<E>>>,
+    callback: fn(E) -> bool,
+    cached: Option<Arc<T::Assoc>>,
+    output: Vec<<T as Trait>::Out>,
+    nested: Vec<<T::Assoc as Trait>::Out>,
+    #[debug(bound = "Opaque<U>: Debug")]
+    opaque: Opaque<U>,
+}
@@ -0,0 +1,61 @@
+impl<T> ::core::fmt::Debug for Session<T>
+where
+    T: ::core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self { user_id, remote_addr, timing } => {
+                let builder = &mut f.debug_struct("Session");
+                builder.field("userId", user_id);
+                builder.field("ip", remote_addr);
+                {
+                    #[diagnostic::on_unimplemented(
+                        message = "`{Self}` cannot be flattened into another struct",
+                        label = "not `#[debug(flattenable)]`",
+                        note = "add `#[debug(flattenable)]` to the definition of `{Self}`"
+                    )]
+                    trait DebugFlattenable {}
+                    #[allow(dead_code)]
+                    trait DebugFlatten {
+                        fn __debug_flatten(
+                            &self,
+                            _: &mut ::core::fmt::DebugStruct<'_, '_>,
+                        )
+                        where
+                            Self: DebugFlattenable,
+                        {}
+                    }
+                    impl<T: ?::core::marker::Sized> DebugFlatten for T {}
+                    timing.__debug_flatten(builder);
+                }
+                builder.finish()
+            }
+        }
+    }
+}
+
+impl ::core::fmt::Debug for Timing {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self { started_at, elapsed } => {
+                f
+                    .debug_struct("Timing")
+                    .field("started_at", started_at)
+                    .field("elapsed", &::core::format_args!("{}ms", elapsed))
+                    .finish()
+            }
+        }
+    }
+}
+impl Timing {
+    #[doc(hidden)]
+    #[allow(dead_code)]
+    pub fn __debug_flatten(&self, builder: &mut ::core::fmt::DebugStruct<'_, '_>) {
+        match self {
+            Self { started_at, elapsed } => {
+                builder.field("started_at", started_at);
+                builder.field("elapsed", &::core::format_args!("{}ms", elapsed));
+            }
+        }
+    }
+}
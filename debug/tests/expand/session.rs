@@ -0,0 +1,19 @@
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(rename_all = "camelCase")]
+pub struct Session<T> {
+    user_id: u32,
+    #[debug(rename = "ip")]
+    remote_addr: T,
+    #[debug(flatten)]
+    timing: Timing,
+}
+
+#[derive(CustomDebug)]
+#[debug(flattenable)]
+pub struct Timing {
+    started_at: u64,
+    #[debug = "{}ms"]
+    elapsed: u64,
+}
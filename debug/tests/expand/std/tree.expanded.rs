@@ -0,0 +1,53 @@
+impl<T> ::core::fmt::Debug for Node<T>
+where
+    T: ::core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        ::std::thread_local! {
+            static DEBUG_DEPTH : ::core::cell::Cell < usize > = const {
+            ::core::cell::Cell::new(0) };
+        }
+        struct LeaveDepth;
+        impl ::core::ops::Drop for LeaveDepth {
+            fn drop(&mut self) {
+                DEBUG_DEPTH.with(|cell| cell.set(cell.get() - 1));
+            }
+        }
+        let depth = DEBUG_DEPTH.with(::core::cell::Cell::get);
+        if depth >= 8 {
+            return f.write_str("…");
+        }
+        DEBUG_DEPTH.with(|cell| cell.set(cell.get() + 1));
+        let _leave_depth = LeaveDepth;
+        ::std::thread_local! {
+            static DEBUG_VISITING : ::core::cell::RefCell < ::std::vec::Vec < (* const
+            (), & 'static str) >, > = const {
+            ::core::cell::RefCell::new(::std::vec::Vec::new()) };
+        }
+        struct LeaveVisiting;
+        impl ::core::ops::Drop for LeaveVisiting {
+            fn drop(&mut self) {
+                DEBUG_VISITING.with(|stack| stack.borrow_mut().pop());
+            }
+        }
+        let visiting = (
+            self as *const Self as *const (),
+            ::core::any::type_name::<Self>(),
+        );
+        if DEBUG_VISITING.with(|stack| stack.borrow().contains(&visiting)) {
+            return f.write_str("<cycle>");
+        }
+        DEBUG_VISITING.with(|stack| stack.borrow_mut().push(visiting));
+        let _leave_visiting = LeaveVisiting;
+        match self {
+            Self { value, parent, children } => {
+                f
+                    .debug_struct("Node")
+                    .field("value", value)
+                    .field("parent", parent)
+                    .field("children", children)
+                    .finish()
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+#[derive(CustomDebug)]
+#[debug(max_depth = 8, detect_cycles)]
+pub struct Node<T> {
+    value: T,
+    parent: Option<Rc<RefCell<Node<T>>>>,
+    children: Vec<Rc<RefCell<Node<T>>>>,
+}
@@ -0,0 +1,85 @@
+impl<T, W> ::core::fmt::Debug for Reading<T, W>
+where
+    T: ::core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self { sensor, level, value, key: _, writer: _ } => {
+                f
+                    .debug_struct("Reading")
+                    .field("sensor", sensor)
+                    .field("level", &::core::format_args!("{}%", level))
+                    .field("value", value)
+                    .field("key", &::core::format_args!("{}", "[REDACTED]"))
+                    .finish_non_exhaustive()
+            }
+        }
+    }
+}
+impl<T, W> ::ufmt::uDebug for Reading<T, W>
+where
+    T: ::ufmt::uDebug,
+{
+    fn fmt<W_>(
+        &self,
+        f: &mut ::ufmt::Formatter<'_, W_>,
+    ) -> ::core::result::Result<(), W_::Error>
+    where
+        W_: ::ufmt::uWrite + ?::core::marker::Sized,
+    {
+        match self {
+            Self { sensor, level, value, key: _, writer: _ } => {
+                f.write_str("Reading { sensor: ")?;
+                ::ufmt::uDebug::fmt(sensor, f)?;
+                f.write_str(", level: ")?;
+                ::ufmt::uwrite!(f, "{}", level)?;
+                f.write_str("%, value: ")?;
+                ::ufmt::uDebug::fmt(value, f)?;
+                f.write_str(", key: [REDACTED], .. }")?;
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+}
+
+impl ::core::fmt::Debug for Event {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self::Boot => f.write_str("Boot"),
+            #[allow(unused_variables)]
+            Self::Fault { code, at } => ::core::write!(f, "fault {code}"),
+            Self::Sample(_0, _) => {
+                f
+                    .debug_tuple("Sample")
+                    .field(_0)
+                    .field(&::core::format_args!("{}", "<raw>"))
+                    .finish()
+            }
+        }
+    }
+}
+impl ::ufmt::uDebug for Event {
+    fn fmt<W>(
+        &self,
+        f: &mut ::ufmt::Formatter<'_, W>,
+    ) -> ::core::result::Result<(), W::Error>
+    where
+        W: ::ufmt::uWrite + ?::core::marker::Sized,
+    {
+        match self {
+            Self::Boot => {
+                f.write_str("Boot")?;
+            }
+            Self::Fault { code, .. } => {
+                f.write_str("fault ")?;
+                ::ufmt::uwrite!(f, "{}", code)?;
+            }
+            Self::Sample(_0, _) => {
+                f.write_str("Sample(")?;
+                ::ufmt::uDebug::fmt(_0, f)?;
+                f.write_str(", <raw>)")?;
+            }
+        }
+        ::core::result::Result::Ok(())
+    }
+}
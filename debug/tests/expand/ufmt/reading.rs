@@ -0,0 +1,23 @@
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(ufmt, non_exhaustive)]
+pub struct Reading<T, W> {
+    sensor: &'static str,
+    #[debug = "{}%"]
+    level: u8,
+    value: T,
+    #[debug(redact)]
+    key: [u8; 4],
+    #[debug(skip)]
+    writer: W,
+}
+
+#[derive(CustomDebug)]
+#[debug(ufmt)]
+pub enum Event {
+    Boot,
+    #[debug = "fault {code}"]
+    Fault { code: u16, at: u64 },
+    Sample(u32, #[debug(redact = "<raw>")] u32),
+}
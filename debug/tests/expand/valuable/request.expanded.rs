@@ -0,0 +1,110 @@
+impl<T> ::core::fmt::Debug for Request<T> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self { method, token: _, buffer: _ } => {
+                f
+                    .debug_struct("Request")
+                    .field("method", method)
+                    .field("token", &::core::format_args!("{}", "[REDACTED]"))
+                    .finish()
+            }
+        }
+    }
+}
+impl<T> ::valuable::Valuable for Request<T> {
+    fn as_value(&self) -> ::valuable::Value<'_> {
+        ::valuable::Value::Structable(self)
+    }
+    fn visit(&self, visit: &mut dyn ::valuable::Visit) {
+        match self {
+            Self { method, token: _, buffer: _ } => {
+                const FIELDS: &[::valuable::NamedField<'static>] = &[
+                    ::valuable::NamedField::new("method"),
+                    ::valuable::NamedField::new("token"),
+                ];
+                visit
+                    .visit_named_fields(
+                        &::valuable::NamedValues::new(
+                            FIELDS,
+                            &[
+                                ::valuable::Valuable::as_value(method),
+                                ::valuable::Value::String("[REDACTED]"),
+                            ],
+                        ),
+                    )
+            }
+        }
+    }
+}
+impl<T> ::valuable::Structable for Request<T> {
+    fn definition(&self) -> ::valuable::StructDef<'_> {
+        const FIELDS: &[::valuable::NamedField<'static>] = &[
+            ::valuable::NamedField::new("method"),
+            ::valuable::NamedField::new("token"),
+        ];
+        ::valuable::StructDef::new_static("Request", ::valuable::Fields::Named(FIELDS))
+    }
+}
+
+impl ::core::fmt::Debug for Peer {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self::Local => f.write_str("Local"),
+            Self::Remote(_0, _) => f.debug_tuple("Remote").field(_0).finish(),
+            Self::Named { host } => f.debug_struct("Named").field("host", host).finish(),
+        }
+    }
+}
+impl ::valuable::Valuable for Peer {
+    fn as_value(&self) -> ::valuable::Value<'_> {
+        ::valuable::Value::Enumerable(self)
+    }
+    fn visit(&self, visit: &mut dyn ::valuable::Visit) {
+        match self {
+            Self::Local => visit.visit_unnamed_fields(&[]),
+            Self::Remote(_0, _) => {
+                visit.visit_unnamed_fields(&[::valuable::Valuable::as_value(_0)])
+            }
+            Self::Named { host } => {
+                const FIELDS: &[::valuable::NamedField<'static>] = &[
+                    ::valuable::NamedField::new("host"),
+                ];
+                visit
+                    .visit_named_fields(
+                        &::valuable::NamedValues::new(
+                            FIELDS,
+                            &[::valuable::Valuable::as_value(host)],
+                        ),
+                    )
+            }
+        }
+    }
+}
+impl ::valuable::Enumerable for Peer {
+    fn definition(&self) -> ::valuable::EnumDef<'_> {
+        const VARIANTS: &[::valuable::VariantDef<'static>] = &[
+            ::valuable::VariantDef::new("Local", ::valuable::Fields::Unnamed(0usize)),
+            ::valuable::VariantDef::new("Remote", ::valuable::Fields::Unnamed(1usize)),
+            ::valuable::VariantDef::new(
+                "Named",
+                ::valuable::Fields::Named(&[::valuable::NamedField::new("host")]),
+            ),
+        ];
+        ::valuable::EnumDef::new_static("Peer", VARIANTS)
+    }
+    fn variant(&self) -> ::valuable::Variant<'_> {
+        const VARIANTS: &[::valuable::VariantDef<'static>] = &[
+            ::valuable::VariantDef::new("Local", ::valuable::Fields::Unnamed(0usize)),
+            ::valuable::VariantDef::new("Remote", ::valuable::Fields::Unnamed(1usize)),
+            ::valuable::VariantDef::new(
+                "Named",
+                ::valuable::Fields::Named(&[::valuable::NamedField::new("host")]),
+            ),
+        ];
+        match self {
+            Self::Local { .. } => ::valuable::Variant::Static(&VARIANTS[0usize]),
+            Self::Remote { .. } => ::valuable::Variant::Static(&VARIANTS[1usize]),
+            Self::Named { .. } => ::valuable::Variant::Static(&VARIANTS[2usize]),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(valuable)]
+pub struct Request<T> {
+    method: &'static str,
+    #[debug(redact)]
+    token: T,
+    #[debug(skip)]
+    buffer: Vec<u8>,
+}
+
+#[derive(CustomDebug)]
+#[debug(valuable)]
+pub enum Peer {
+    Local,
+    Remote(&'static str, #[debug(skip)] u16),
+    Named { host: String },
+}
@@ -1,12 +1,43 @@
 #[test]
 fn tests() {
     let t = trybuild::TestCases::new();
-    //t.pass("tests/01-parse.rs");
-    //t.pass("tests/02-impl-debug.rs");
-    //t.pass("tests/03-custom-format.rs");
-    //t.pass("tests/04-type-parameter.rs");
-    //t.pass("tests/05-phantom-data.rs");
-    //t.pass("tests/06-bound-trouble.rs");
-    //t.pass("tests/07-associated-type.rs");
-    //t.pass("tests/08-escape-hatch.rs");
+    t.pass("tests/01-parse.rs");
+    t.pass("tests/02-impl-debug.rs");
+    t.pass("tests/03-custom-format.rs");
+    t.pass("tests/04-type-parameter.rs");
+    t.pass("tests/05-phantom-data.rs");
+    t.pass("tests/06-bound-trouble.rs");
+    t.pass("tests/07-associated-type.rs");
+    t.pass("tests/08-escape-hatch.rs");
+    t.pass("tests/09-enum.rs");
+    t.pass("tests/10-tuple-and-unit-struct.rs");
+    t.pass("tests/11-skip-and-redact.rs");
+    t.pass("tests/12-with-function.rs");
+    t.pass("tests/13-display.rs");
+    t.compile_fail("tests/14-display-unknown-field.rs");
+    t.pass("tests/15-output-modes.rs");
+    t.pass("tests/16-nested-bounds.rs");
+    t.pass("tests/17-redefined-prelude-types.rs");
+    t.pass("tests/20-rename-and-flatten.rs");
+    t.compile_fail("tests/21-field-format-errors.rs");
+    t.compile_fail("tests/22-variant-format-errors.rs");
+    t.pass("tests/24-truncate.rs");
+    t.compile_fail("tests/27-flatten-errors.rs");
+
+    #[cfg(feature = "std")]
+    t.pass("tests/23-recursive-types.rs");
+    #[cfg(not(feature = "std"))]
+    t.compile_fail("tests/29-recursion-without-std.rs");
+    #[cfg(feature = "defmt")]
+    t.pass("tests/18-defmt.rs");
+    #[cfg(feature = "defmt")]
+    t.compile_fail("tests/30-defmt-unsupported-spec.rs");
+    #[cfg(feature = "valuable")]
+    t.pass("tests/19-valuable.rs");
+    #[cfg(feature = "ufmt")]
+    t.pass("tests/25-ufmt.rs");
+    #[cfg(feature = "ufmt")]
+    t.compile_fail("tests/26-ufmt-unsupported-spec.rs");
+    #[cfg(all(feature = "defmt", feature = "ufmt", feature = "valuable"))]
+    t.compile_fail("tests/28-flatten-other-backends.rs");
 }
@@ -7,8 +7,10 @@
 //     $ cargo run
 
 use derive_builder::Builder;
+use std::env;
 
 #[derive(Builder)]
+#[builder(from_args)]
 pub struct Command {
     executable: String,
     #[builder(each = "arg")]
@@ -28,4 +30,26 @@ fn main() {
 
     assert_eq!(command.executable, "cargo");
     assert_eq!(command.args, vec!["build", "--release"]);
+
+    // The same command from command line arguments, e.g.
+    //     $ cargo run -- --executable cargo --arg build --arg --release
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        match CommandBuilder::from_args(args).and_then(|mut builder| builder.build()) {
+            Ok(command) => {
+                if let Some(dir) = &command.current_dir {
+                    println!("cd {}", dir);
+                }
+                let words: Vec<&str> = command
+                    .env
+                    .iter()
+                    .chain(Some(&command.executable))
+                    .chain(&command.args)
+                    .map(String::as_str)
+                    .collect();
+                println!("{}", words.join(" "));
+            }
+            Err(err) => eprintln!("error: {}", err),
+        }
+    }
 }
@@ -0,0 +1,226 @@
+use proc_macro2::{Delimiter, Group, Literal, Spacing, Span, TokenTree};
+use syn::Lit;
+
+use crate::{index, Bindings, Item, Paste};
+
+/// The value of a `~{...}` expression, along with the suffix of the integer
+/// literals in it, if any, and how the first variable in it is pasted.
+pub struct Value {
+    pub value: i128,
+    pub suffix: String,
+    pub paste: Paste,
+}
+
+impl Value {
+    /// The value as an integer literal at the given span.
+    pub fn to_literal(&self, span: Span) -> Literal {
+        let mut literal = if self.suffix.is_empty() {
+            Literal::i128_unsuffixed(self.value)
+        } else {
+            format!("{}{}", self.value, self.suffix)
+                .parse()
+                .expect("integer literal")
+        };
+        literal.set_span(span);
+        literal
+    }
+}
+
+/// Evaluates the constant expression in braces after a `~`: integers, the
+/// integer variables of the seq and `#index`, combined with `+ - * / % << >>`
+/// at Rust's precedence, unary minus and parentheses. A suffix on an integer or
+/// on the range of a variable carries over to the result, and so does the
+/// `#[seq(...)]` of the first variable when the result is pasted.
+pub fn eval(group: &Group, bindings: &Bindings) -> syn::Result<Value> {
+    let mut parser = Parser {
+        tokens: group.stream().into_iter().collect(),
+        pos: 0,
+        bindings,
+        span: group.span(),
+        suffix: String::new(),
+        paste: None,
+    };
+    let value = parser.complete()?;
+    Ok(Value {
+        value,
+        suffix: parser.suffix,
+        paste: parser.paste.unwrap_or_default(),
+    })
+}
+
+#[derive(Clone, Copy)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Shl,
+    Shr,
+}
+
+impl Op {
+    fn precedence(self) -> u8 {
+        match self {
+            Op::Shl | Op::Shr => 1,
+            Op::Add | Op::Sub => 2,
+            Op::Mul | Op::Div | Op::Rem => 3,
+        }
+    }
+}
+
+struct Parser<'a> {
+    tokens: Vec<TokenTree>,
+    pos: usize,
+    bindings: &'a Bindings<'a>,
+    /// Where errors about the expression as a whole are reported.
+    span: Span,
+    suffix: String,
+    paste: Option<Paste>,
+}
+
+impl Parser<'_> {
+    /// The whole of the tokens as one expression.
+    fn complete(&mut self) -> syn::Result<i128> {
+        let value = self.expr(0)?;
+        match self.tokens.get(self.pos) {
+            Some(token) => Err(syn::Error::new(token.span(), "expected an operator")),
+            None => Ok(value),
+        }
+    }
+
+    /// Operators binding at least as tightly as `min_precedence`, left to
+    /// right.
+    fn expr(&mut self, min_precedence: u8) -> syn::Result<i128> {
+        let mut lhs = self.unary()?;
+        while let Some((op, len)) = self.peek_op() {
+            if op.precedence() < min_precedence {
+                break;
+            }
+            let span = self.tokens[self.pos].span();
+            self.pos += len;
+            let rhs = self.expr(op.precedence() + 1)?;
+            lhs = apply(op, lhs, rhs)
+                .ok_or_else(|| syn::Error::new(span, "overflow or division by zero"))?;
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> syn::Result<i128> {
+        match self.tokens.get(self.pos) {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '-' => {
+                let span = punct.span();
+                self.pos += 1;
+                self.unary()?
+                    .checked_neg()
+                    .ok_or_else(|| syn::Error::new(span, "overflow"))
+            }
+            _ => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> syn::Result<i128> {
+        let token = match self.tokens.get(self.pos) {
+            Some(token) => token.clone(),
+            None => return Err(syn::Error::new(self.span, "expected an expression")),
+        };
+        if let Some(ident) = index(&self.tokens[self.pos..]) {
+            self.pos += 2;
+            return match self.bindings.index {
+                Some(index) => Ok(index as i128),
+                None => Err(syn::Error::new(ident.span(), "no `#index` here")),
+            };
+        }
+        self.pos += 1;
+        match &token {
+            TokenTree::Ident(ident) => match self.bindings.get(ident) {
+                Some(Item::Int(int)) => {
+                    self.merge_suffix(&int.suffix, ident.span())?;
+                    self.paste.get_or_insert(int.paste);
+                    Ok(i128::from(int.value))
+                }
+                Some(_) => Err(syn::Error::new(ident.span(), "not an integer variable")),
+                None => Err(syn::Error::new(ident.span(), "not a variable of this seq")),
+            },
+            TokenTree::Literal(literal) => {
+                let literal = match Lit::new(literal.clone()) {
+                    Lit::Int(literal) => literal,
+                    _ => {
+                        return Err(syn::Error::new(
+                            literal.span(),
+                            "expected an integer literal",
+                        ))
+                    }
+                };
+                let value = literal.base10_parse()?;
+                self.merge_suffix(literal.suffix(), literal.span())?;
+                Ok(value)
+            }
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+                let mut parser = Parser {
+                    tokens: group.stream().into_iter().collect(),
+                    pos: 0,
+                    bindings: self.bindings,
+                    span: group.span(),
+                    suffix: std::mem::take(&mut self.suffix),
+                    paste: self.paste,
+                };
+                let value = parser.complete()?;
+                self.suffix = parser.suffix;
+                self.paste = parser.paste;
+                Ok(value)
+            }
+            _ => Err(syn::Error::new(
+                token.span(),
+                "expected an integer, a variable or `(`",
+            )),
+        }
+    }
+
+    /// Carries the suffix of an integer or variable over to the result.
+    fn merge_suffix(&mut self, suffix: &str, span: Span) -> syn::Result<()> {
+        if !suffix.is_empty() {
+            if !self.suffix.is_empty() && self.suffix != suffix {
+                return Err(syn::Error::new(span, "mismatched suffixes"));
+            }
+            self.suffix = suffix.to_owned();
+        }
+        Ok(())
+    }
+
+    /// The operator at the current position and how many tokens it spans.
+    fn peek_op(&self) -> Option<(Op, usize)> {
+        let punct = match self.tokens.get(self.pos) {
+            Some(TokenTree::Punct(punct)) => punct,
+            _ => return None,
+        };
+        let next = match self.tokens.get(self.pos + 1) {
+            Some(TokenTree::Punct(next)) if punct.spacing() == Spacing::Joint => {
+                Some(next.as_char())
+            }
+            _ => None,
+        };
+        match (punct.as_char(), next) {
+            ('+', _) => Some((Op::Add, 1)),
+            ('-', _) => Some((Op::Sub, 1)),
+            ('*', _) => Some((Op::Mul, 1)),
+            ('/', _) => Some((Op::Div, 1)),
+            ('%', _) => Some((Op::Rem, 1)),
+            ('<', Some('<')) => Some((Op::Shl, 2)),
+            ('>', Some('>')) => Some((Op::Shr, 2)),
+            _ => None,
+        }
+    }
+}
+
+fn apply(op: Op, lhs: i128, rhs: i128) -> Option<i128> {
+    match op {
+        Op::Add => lhs.checked_add(rhs),
+        Op::Sub => lhs.checked_sub(rhs),
+        Op::Mul => lhs.checked_mul(rhs),
+        Op::Div => lhs.checked_div(rhs),
+        Op::Rem => lhs.checked_rem(rhs),
+        Op::Shl => lhs.checked_shl(u32::try_from(rhs).ok()?),
+        Op::Shr => lhs.checked_shr(u32::try_from(rhs).ok()?),
+    }
+}
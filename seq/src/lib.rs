@@ -1,8 +1,744 @@
-use proc_macro::TokenStream;
+use proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::{
+    braced, bracketed, parenthesized, token, Attribute, Lit, LitChar, LitInt, Meta, NestedMeta,
+    Token,
+};
+
+mod expr;
+
+/// `N in 0..16 { ... }` or `R in 0..4, C in 0..4 { ... }`: the variables, the
+/// values they take and the body repeated for each of their combinations. A
+/// variable over integers may be preceded by `#[seq(pad = 2, radix = 16)]` to
+/// choose how it is pasted into identifiers.
+struct Seq {
+    vars: Vec<Var>,
+    body: TokenStream,
+}
+
+struct Var {
+    ident: Ident,
+    values: Vec<Item>,
+}
+
+/// One of the values a variable takes.
+#[derive(Clone)]
+enum Item {
+    Int(Int),
+    Char(char),
+    /// An element of a `[...]` list, as written.
+    Tokens(TokenStream),
+}
+
+/// An integer of a range, remembering how the range was written.
+#[derive(Clone)]
+struct Int {
+    value: u64,
+    /// The suffix of the range's literals, as in `0u8..16u8`.
+    suffix: String,
+    /// The radix of the range's start, as in `0x00..0x10`.
+    radix: u32,
+    paste: Paste,
+}
+
+/// How an integer is pasted into identifiers, from `#[seq(...)]`: in decimal
+/// without padding unless specified.
+#[derive(Clone, Copy)]
+struct Paste {
+    pad: usize,
+    radix: u32,
+}
+
+impl Default for Paste {
+    fn default() -> Self {
+        Paste { pad: 0, radix: 10 }
+    }
+}
+
+impl Int {
+    /// The value written in the radix and with the suffix of the range.
+    fn to_literal(&self) -> Literal {
+        let prefix = match self.radix {
+            16 => "0x",
+            8 => "0o",
+            2 => "0b",
+            _ => "",
+        };
+        format!(
+            "{}{}{}",
+            prefix,
+            digits(self.value, self.radix, 0),
+            self.suffix
+        )
+        .parse()
+        .expect("integer literal")
+    }
+
+    fn to_ident_part(&self) -> String {
+        digits(self.value, self.paste.radix, self.paste.pad)
+    }
+}
+
+/// The digits of the value in the radix, in upper case and padded with zeros
+/// to at least `pad` of them.
+fn digits(value: u64, radix: u32, pad: usize) -> String {
+    let digits = match radix {
+        16 => format!("{:X}", value),
+        8 => format!("{:o}", value),
+        2 => format!("{:b}", value),
+        _ => value.to_string(),
+    };
+    format!("{:0>pad$}", digits, pad = pad)
+}
+
+impl Item {
+    /// The item in place of the variable written at the span.
+    fn to_tokens(&self, span: Span) -> TokenStream {
+        let literal = match self {
+            Item::Int(int) => int.to_literal(),
+            Item::Char(value) => Literal::character(*value),
+            Item::Tokens(tokens) => {
+                // Several tokens stay together like a macro_rules fragment.
+                return match tokens.clone().into_iter().count() {
+                    1 => tokens.clone(),
+                    _ => TokenStream::from(TokenTree::Group(Group::new(
+                        Delimiter::None,
+                        tokens.clone(),
+                    ))),
+                };
+            }
+        };
+        let mut literal = literal;
+        literal.set_span(span);
+        TokenStream::from(TokenTree::Literal(literal))
+    }
+
+    /// The item as part of an identifier.
+    fn to_ident_part(&self, span: Span) -> syn::Result<String> {
+        match self {
+            Item::Int(int) => Ok(int.to_ident_part()),
+            Item::Char(value) => Ok(value.to_string()),
+            Item::Tokens(tokens) => {
+                let mut tokens = tokens.clone().into_iter();
+                match (tokens.next(), tokens.next()) {
+                    (Some(TokenTree::Ident(ident)), None) => Ok(ident.unraw().to_string()),
+                    (Some(TokenTree::Literal(literal)), None) => Ok(literal.to_string()),
+                    _ => Err(syn::Error::new(
+                        span,
+                        "only a single identifier or literal can be pasted into an identifier",
+                    )),
+                }
+            }
+        }
+    }
+}
+
+/// The item each variable stands for in one repetition of the body, and the
+/// number of the repetition for `#index`.
+struct Bindings<'a> {
+    vars: Vec<(&'a Ident, &'a Item)>,
+    /// `None` within a nested `seq!`, whose `#index` is its own.
+    index: Option<usize>,
+    /// The variables of the nested `seq!`s being substituted into, left for
+    /// them to substitute.
+    deferred: Vec<&'a Ident>,
+}
+
+impl Bindings<'_> {
+    fn get(&self, ident: &Ident) -> Option<&Item> {
+        self.vars
+            .iter()
+            .find(|(var, _)| *var == ident)
+            .map(|(_, item)| *item)
+    }
+
+    /// Whether the tokens refer to a variable or `#index` of a nested `seq!`.
+    fn defers(&self, tokens: TokenStream) -> bool {
+        let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+        (0..tokens.len()).any(|i| match &tokens[i] {
+            _ if index(&tokens[i..]).is_some() => self.index.is_none(),
+            TokenTree::Ident(ident) => self.deferred.contains(&ident),
+            TokenTree::Group(group) => self.defers(group.stream()),
+            _ => false,
+        })
+    }
+}
+
+impl Parse for Seq {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut vars = Vec::new();
+        loop {
+            let attrs = input.call(Attribute::parse_outer)?;
+            let paste = parse_paste(&attrs)?;
+            let ident: Ident = input.parse()?;
+            if vars.iter().any(|var: &Var| var.ident == ident) {
+                return Err(syn::Error::new(ident.span(), "variable bound twice"));
+            }
+            input.parse::<Token![in]>()?;
+            let values = if input.peek(token::Paren) {
+                let content;
+                parenthesized!(content in input);
+                let values = parse_values(&content, paste)?;
+                parse_adapters(input, values)?
+            } else {
+                parse_values(input, paste)?
+            };
+            if let (Some(attr), Some(Item::Char(_) | Item::Tokens(_))) =
+                (attrs.first(), values.first())
+            {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "`#[seq(...)]` only applies to integer ranges",
+                ));
+            }
+            vars.push(Var { ident, values });
+            if input.peek(token::Brace) {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        let content;
+        braced!(content in input);
+        let body: TokenStream = content.parse()?;
+        Ok(Seq { vars, body })
+    }
+}
+
+impl Seq {
+    /// Every combination of values, the last variable varying fastest as in
+    /// nested loops.
+    fn combinations(&self) -> Vec<Bindings<'_>> {
+        let mut combinations = vec![Vec::new()];
+        for var in &self.vars {
+            combinations = combinations
+                .into_iter()
+                .flat_map(|vars| {
+                    var.values.iter().map(move |item| {
+                        let mut vars = vars.clone();
+                        vars.push((&var.ident, item));
+                        vars
+                    })
+                })
+                .collect();
+        }
+        combinations
+            .into_iter()
+            .enumerate()
+            .map(|(index, vars)| Bindings {
+                vars,
+                index: Some(index),
+                deferred: Vec::new(),
+            })
+            .collect()
+    }
+}
+
+/// `0..16`, `0..=15`, `'a'..='f'` or a list of items `[u8, u16, u32]`. The
+/// items of a list are split at its commas, except for those within angle
+/// brackets, as in `HashMap<K, V>`.
+fn parse_values(input: ParseStream, paste: Paste) -> syn::Result<Vec<Item>> {
+    if input.peek(token::Bracket) {
+        let content;
+        bracketed!(content in input);
+        let tokens: Vec<TokenTree> = content.parse::<TokenStream>()?.into_iter().collect();
+        let mut items = Vec::new();
+        let mut item = TokenStream::new();
+        let mut depth = 0usize;
+        for (i, token) in tokens.iter().enumerate() {
+            if let TokenTree::Punct(punct) = token {
+                match punct.as_char() {
+                    ',' if depth == 0 => {
+                        items.push(Item::Tokens(std::mem::take(&mut item)));
+                        continue;
+                    }
+                    '<' if !is_joined(&tokens, i) => depth += 1,
+                    '>' if !is_joined(&tokens, i) => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+            }
+            item.extend(Some(token.clone()));
+        }
+        if !item.is_empty() {
+            items.push(Item::Tokens(item));
+        }
+        return Ok(items);
+    }
+
+    if input.peek(LitChar) {
+        let start: LitChar = input.parse()?;
+        let inclusive = parse_range_limits(input)?;
+        let end: LitChar = input.parse()?;
+        let (start, end) = (start.value(), end.value());
+        return Ok(if inclusive {
+            (start..=end).map(Item::Char).collect()
+        } else {
+            (start..end).map(Item::Char).collect()
+        });
+    }
+
+    let start: LitInt = input.parse()?;
+    let inclusive = parse_range_limits(input)?;
+    let end: LitInt = input.parse()?;
+    let suffix = match (start.suffix(), end.suffix()) {
+        (start, end) if start == end || end.is_empty() => start,
+        ("", end) => end,
+        _ => return Err(syn::Error::new(end.span(), "mismatched suffixes")),
+    }
+    .to_owned();
+    let radix = match start.to_string().get(..2) {
+        Some("0x") => 16,
+        Some("0o") => 8,
+        Some("0b") => 2,
+        _ => 10,
+    };
+    let start = start.base10_parse()?;
+    let end = match (end.base10_parse::<u64>()?, inclusive) {
+        (value, false) => value,
+        (value, true) => value
+            .checked_add(1)
+            .ok_or_else(|| syn::Error::new(end.span(), "range end out of range"))?,
+    };
+    Ok((start..end)
+        .map(|value| {
+            Item::Int(Int {
+                value,
+                suffix: suffix.clone(),
+                radix,
+                paste,
+            })
+        })
+        .collect())
+}
+
+/// `#[seq(pad = 2, radix = 16)]`, either argument optional.
+fn parse_paste(attrs: &[Attribute]) -> syn::Result<Paste> {
+    let mut paste = Paste::default();
+    for attr in attrs {
+        if !attr.path.is_ident("seq") {
+            return Err(syn::Error::new_spanned(&attr.path, "expected `seq`"));
+        }
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(syn::Error::new_spanned(meta, "expected `seq(...)`")),
+        };
+        for nested in list.nested {
+            let (name_value, lit) = match nested {
+                NestedMeta::Meta(Meta::NameValue(name_value)) => match &name_value.lit {
+                    Lit::Int(lit) => (name_value.clone(), lit.clone()),
+                    lit => return Err(syn::Error::new_spanned(lit, "expected an integer")),
+                },
+                nested => {
+                    return Err(syn::Error::new_spanned(
+                        nested,
+                        "expected `pad = ...` or `radix = ...`",
+                    ))
+                }
+            };
+            if name_value.path.is_ident("pad") {
+                paste.pad = lit.base10_parse()?;
+            } else if name_value.path.is_ident("radix") {
+                paste.radix = lit.base10_parse()?;
+                if ![2, 8, 10, 16].contains(&paste.radix) {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "expected a radix of 2, 8, 10 or 16",
+                    ));
+                }
+            } else {
+                return Err(syn::Error::new_spanned(
+                    name_value.path,
+                    "expected `pad` or `radix`",
+                ));
+            }
+        }
+    }
+    Ok(paste)
+}
+
+/// `..` or `..=`, telling whether the range is inclusive.
+fn parse_range_limits(input: ParseStream) -> syn::Result<bool> {
+    if input.peek(Token![..=]) {
+        input.parse::<Token![..=]>()?;
+        Ok(true)
+    } else {
+        input.parse::<Token![..]>()?;
+        Ok(false)
+    }
+}
+
+/// The iterator adapters `.step_by(n)` and `.rev()` on a parenthesized range,
+/// applied in the order they are written.
+fn parse_adapters(input: ParseStream, mut values: Vec<Item>) -> syn::Result<Vec<Item>> {
+    while input.peek(Token![.]) {
+        input.parse::<Token![.]>()?;
+        let adapter: Ident = input.parse()?;
+        let content;
+        parenthesized!(content in input);
+        if adapter == "step_by" {
+            let step: LitInt = content.parse()?;
+            let step = match step.base10_parse::<usize>()? {
+                0 => return Err(syn::Error::new(step.span(), "step must not be zero")),
+                step => step,
+            };
+            values = values.into_iter().step_by(step).collect();
+        } else if adapter == "rev" {
+            values.reverse();
+        } else {
+            return Err(syn::Error::new(
+                adapter.span(),
+                "expected `step_by(n)` or `rev()`",
+            ));
+        }
+        if !content.is_empty() {
+            return Err(content.error("unexpected argument"));
+        }
+    }
+    Ok(values)
+}
 
 #[proc_macro]
-pub fn seq(input: TokenStream) -> TokenStream {
-    let _ = input;
+pub fn seq(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    proc_macro::TokenStream::from(expand(TokenStream::from(input)))
+}
+
+fn expand(input: TokenStream) -> TokenStream {
+    let seq: Seq = match syn::parse2(input) {
+        Ok(seq) => seq,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    // Only the `#(...)*` sections are repeated if there are any, otherwise
+    // the whole body is.
+    let combinations = seq.combinations();
+    let mut has_sections = false;
+    let expanded = match repeat_sections(seq.body.clone(), &combinations, &mut has_sections) {
+        Ok(expanded) => expanded,
+        Err(err) => return err.to_compile_error(),
+    };
+    if has_sections {
+        expanded
+    } else {
+        combinations
+            .iter()
+            .map(|bindings| substitute(seq.body.clone(), bindings))
+            .collect::<syn::Result<_>>()
+            .unwrap_or_else(syn::Error::into_compile_error)
+    }
+}
+
+/// The body with every `#(...)*` section replaced by its contents repeated
+/// once per combination of values. The sections of a nested `seq!` are its
+/// own.
+fn repeat_sections(
+    tokens: TokenStream,
+    combinations: &[Bindings],
+    has_sections: &mut bool,
+) -> syn::Result<TokenStream> {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut output = TokenStream::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if let Some(section) = section(&tokens[i..]) {
+            *has_sections = true;
+            for bindings in combinations {
+                output.extend(substitute(section.stream(), bindings)?);
+            }
+            i += 3;
+            continue;
+        }
+        if nested_seq(&tokens[i..]).is_some() {
+            output.extend(tokens[i..i + 3].iter().cloned());
+            i += 3;
+            continue;
+        }
+        output.extend(Some(match &tokens[i] {
+            TokenTree::Group(group) => {
+                let stream = repeat_sections(group.stream(), combinations, has_sections)?;
+                TokenTree::Group(with_stream(group, stream))
+            }
+            token => token.clone(),
+        }));
+        i += 1;
+    }
+    Ok(output)
+}
+
+/// Whether the `<` or `>` at `tokens[i]` is part of `->`, `=>`, `<=` or `>=`
+/// rather than an angle bracket.
+fn is_joined(tokens: &[TokenTree], i: usize) -> bool {
+    let joint = |i: usize, chars: &[char]| match tokens.get(i) {
+        Some(TokenTree::Punct(punct)) => {
+            punct.spacing() == Spacing::Joint && chars.contains(&punct.as_char())
+        }
+        _ => false,
+    };
+    let next_is_eq = matches!(
+        tokens.get(i + 1),
+        Some(TokenTree::Punct(next)) if next.as_char() == '='
+    );
+    let is_gt = matches!(&tokens[i], TokenTree::Punct(punct) if punct.as_char() == '>');
+    (is_gt && i > 0 && joint(i - 1, &['-', '='])) || (joint(i, &['<', '>']) && next_is_eq)
+}
+
+/// The parenthesized group of a `#(...)*` section at the start of the tokens.
+fn section(tokens: &[TokenTree]) -> Option<&Group> {
+    match tokens {
+        [TokenTree::Punct(pound), TokenTree::Group(group), TokenTree::Punct(star), ..]
+            if pound.as_char() == '#'
+                && group.delimiter() == Delimiter::Parenthesis
+                && star.as_char() == '*' =>
+        {
+            Some(group)
+        }
+        _ => None,
+    }
+}
+
+/// One copy of the tokens for a combination of values: each variable becomes
+/// its item, as an integer or char literal or the tokens of a list, and is
+/// pasted into identifiers it is joined to by `~`, as in `Irq~N` or `cell~R~C`.
+/// Both keep the span of what the caller wrote, so errors point at the body
+/// rather than at the macro invocation. `#index` stands for the number of the
+/// repetition.
+///
+/// A constant expression in braces, as in `~{N * 4}` or `reg~{N + 1}`, is
+/// evaluated and stands for its value the same way.
+fn substitute(tokens: TokenStream, bindings: &Bindings) -> syn::Result<TokenStream> {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut output = TokenStream::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if let Some(group) = nested_seq(&tokens[i..]) {
+            output.extend(tokens[i..i + 2].iter().cloned());
+            output.extend(Some(TokenTree::Group(substitute_nested(group, bindings)?)));
+            i += 3;
+            continue;
+        }
+        if let Some(TokenTree::Group(group)) = pasted(&tokens[i..]) {
+            if bindings.defers(group.stream()) {
+                let stream = substitute(group.stream(), bindings)?;
+                output.extend(Some(tokens[i].clone()));
+                output.extend(Some(TokenTree::Group(with_stream(group, stream))));
+            } else {
+                let value = expr::eval(group, bindings)?;
+                output.extend(Some(TokenTree::Literal(value.to_literal(group.span()))));
+            }
+            i += 2;
+            continue;
+        }
+        if let (Some(ident), Some(index)) = (index(&tokens[i..]), bindings.index) {
+            let mut literal = Literal::usize_unsuffixed(index);
+            literal.set_span(ident.span());
+            output.extend(Some(TokenTree::Literal(literal)));
+            i += 2;
+            continue;
+        }
+        let token = match &tokens[i] {
+            TokenTree::Ident(_) if pasted(&tokens[i + 1..]).is_some() => {
+                let mut parts = vec![&tokens[i]];
+                while let Some(token) = pasted(&tokens[i + 1..]) {
+                    parts.push(token);
+                    i += 2;
+                }
+                output.extend(paste(&parts, bindings)?);
+                i += 1;
+                continue;
+            }
+            TokenTree::Ident(ident) if bindings.get(ident).is_some() => {
+                output.extend(bindings.get(ident).unwrap().to_tokens(ident.span()));
+                i += 1;
+                continue;
+            }
+            TokenTree::Group(group) => {
+                let stream = substitute(group.stream(), bindings)?;
+                TokenTree::Group(with_stream(group, stream))
+            }
+            token => token.clone(),
+        };
+        output.extend(Some(token));
+        i += 1;
+    }
+    Ok(output)
+}
+
+/// The group of a nested `seq!` invocation at the start of the tokens.
+fn nested_seq(tokens: &[TokenTree]) -> Option<&Group> {
+    match tokens {
+        [TokenTree::Ident(seq), TokenTree::Punct(bang), TokenTree::Group(group), ..]
+            if seq == "seq" && bang.as_char() == '!' =>
+        {
+            Some(group)
+        }
+        _ => None,
+    }
+}
+
+/// Pastes an identifier and the parts joined to it by `~` into one identifier.
+/// Parts referring to the variables of a nested `seq!` are left to it, joined
+/// by `~` to what could be pasted already, so that in `cell~R~C` the outer
+/// `seq!` pastes `R` and the inner one `C`.
+fn paste(parts: &[&TokenTree], bindings: &Bindings) -> syn::Result<TokenStream> {
+    let span = parts[0].span();
+    // The pasted text up to the first deferred part, then each deferred part
+    // with the text pasted after it.
+    let mut pieces: Vec<(Option<TokenTree>, String)> = vec![(None, String::new())];
+    for part in parts {
+        let text = match part {
+            TokenTree::Ident(ident) => match bindings.get(ident) {
+                Some(item) => item.to_ident_part(ident.span())?,
+                None if bindings.deferred.contains(&ident) => {
+                    pieces.push((Some((*part).clone()), String::new()));
+                    continue;
+                }
+                None => ident.unraw().to_string(),
+            },
+            TokenTree::Group(group) if bindings.defers(group.stream()) => {
+                let stream = substitute(group.stream(), bindings)?;
+                let group = TokenTree::Group(with_stream(group, stream));
+                pieces.push((Some(group), String::new()));
+                continue;
+            }
+            TokenTree::Group(group) => {
+                let value = expr::eval(group, bindings)?;
+                match u64::try_from(value.value) {
+                    Ok(int) => digits(int, value.paste.radix, value.paste.pad),
+                    Err(_) if value.value < 0 => {
+                        return Err(syn::Error::new(
+                            group.span(),
+                            "cannot paste a negative value into an identifier",
+                        ))
+                    }
+                    Err(_) => {
+                        return Err(syn::Error::new(
+                            group.span(),
+                            "value too large to paste into an identifier",
+                        ))
+                    }
+                }
+            }
+            _ => unreachable!(),
+        };
+        pieces.last_mut().unwrap().1.push_str(&text);
+    }
+
+    let mut output = TokenStream::new();
+    for (deferred, text) in pieces {
+        if let Some(part) = deferred {
+            if !output.is_empty() {
+                output.extend(Some(TokenTree::Punct(Punct::new('~', Spacing::Alone))));
+            }
+            output.extend(Some(part));
+        }
+        if text.is_empty() {
+            continue;
+        }
+        let part = if is_identifier(&text) {
+            TokenTree::Ident(Ident::new(&text, span))
+        } else if output.is_empty() {
+            return Err(syn::Error::new(
+                span,
+                format!("`{}` is not a valid identifier", text),
+            ));
+        } else if text.parse::<u64>().is_ok() && (text == "0" || !text.starts_with('0')) {
+            // Digits after a deferred part are left for the nested seq! to
+            // paste as the value of a constant expression.
+            let mut literal = Literal::u64_unsuffixed(text.parse().unwrap());
+            literal.set_span(span);
+            let stream = TokenStream::from(TokenTree::Literal(literal));
+            TokenTree::Group(Group::new(Delimiter::Brace, stream))
+        } else {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "cannot paste `{}` after a variable of a nested `seq!`",
+                    text
+                ),
+            ));
+        };
+        if !output.is_empty() {
+            output.extend(Some(TokenTree::Punct(Punct::new('~', Spacing::Alone))));
+        }
+        output.extend(Some(part));
+    }
+    Ok(output)
+}
+
+/// A nested `seq!` sees the outer variables, in its ranges as well as its body,
+/// except for those it binds again itself, and has an `#index` of its own.
+fn substitute_nested(group: &Group, bindings: &Bindings) -> syn::Result<Group> {
+    let tokens: Vec<TokenTree> = group.stream().into_iter().collect();
+    // The variables are bound in the header, before the braced body.
+    let header = match tokens.split_last() {
+        Some((TokenTree::Group(body), header)) if body.delimiter() == Delimiter::Brace => header,
+        _ => &tokens[..],
+    };
+    let rebound: Vec<&Ident> = header
+        .windows(2)
+        .filter_map(|pair| match pair {
+            [TokenTree::Ident(var), TokenTree::Ident(keyword)] if keyword == "in" => Some(var),
+            _ => None,
+        })
+        .collect();
+    let bindings = Bindings {
+        vars: bindings
+            .vars
+            .iter()
+            .filter(|(var, _)| !rebound.contains(var))
+            .cloned()
+            .collect(),
+        index: None,
+        deferred: bindings.deferred.iter().cloned().chain(rebound).collect(),
+    };
+    Ok(with_stream(group, substitute(group.stream(), &bindings)?))
+}
+
+/// The identifier, or braced expression, following a `~` at the start of the
+/// tokens.
+fn pasted(tokens: &[TokenTree]) -> Option<&TokenTree> {
+    match tokens {
+        [TokenTree::Punct(tilde), token @ TokenTree::Ident(_), ..] if tilde.as_char() == '~' => {
+            Some(token)
+        }
+        [TokenTree::Punct(tilde), token @ TokenTree::Group(group), ..]
+            if tilde.as_char() == '~' && group.delimiter() == Delimiter::Brace =>
+        {
+            Some(token)
+        }
+        _ => None,
+    }
+}
+
+/// The `index` of an `#index` at the start of the tokens.
+fn index(tokens: &[TokenTree]) -> Option<&Ident> {
+    match tokens {
+        [TokenTree::Punct(pound), TokenTree::Ident(ident), ..]
+            if pound.as_char() == '#' && ident == "index" =>
+        {
+            Some(ident)
+        }
+        _ => None,
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(ch) if ch == '_' || ch.is_alphabetic() => {}
+        _ => return false,
+    }
+    name != "_" && chars.all(|ch| ch == '_' || ch.is_alphanumeric())
+}
+
+/// A copy of the group with other contents, keeping its delimiters and span.
+fn with_stream(group: &Group, stream: TokenStream) -> Group {
+    let mut copy = Group::new(group.delimiter(), stream);
+    copy.set_span(group.span());
+    copy
+}
 
-    unimplemented!()
+#[cfg(test)]
+#[test]
+fn expand_snapshots() {
+    snapshot::Snapshots::new("tests/expand").function("seq", expand);
 }
@@ -0,0 +1,18 @@
+// Beyond `prefix~N`, the variable can be pasted in the middle of an identifier
+// as in `f~N~_suffix`, and a body can hold several #(...)* sections, each
+// repeated over the same range.
+
+use seq::seq;
+
+seq!(N in 0..3 {
+    #(
+        const REG~N~_OFFSET: u32 = N * 4;
+    )*
+
+    const OFFSETS: [u32; 3] = [#(REG~N~_OFFSET,)*];
+});
+
+fn main() {
+    assert_eq!(REG2_OFFSET, 8);
+    assert_eq!(OFFSETS, [0, 4, 8]);
+}
@@ -0,0 +1,29 @@
+// A parenthesized range can be followed by the iterator adapters `.step_by(n)`
+// and `.rev()`, which apply to #(...)* sections as much as to the whole body.
+
+use seq::seq;
+
+seq!(N in (0..16).step_by(4) {
+    const CHANNELS: [u32; 4] = [#(N,)*];
+});
+
+seq!(N in (0..4).rev() {
+    fn unwind() -> Vec<u32> {
+        let mut order = Vec::new();
+        #(
+            order.push(N);
+        )*
+        order
+    }
+});
+
+// Adapters apply in order, like on an iterator.
+seq!(N in (0..=8).rev().step_by(3) {
+    const TAPS: [u32; 3] = [#(N,)*];
+});
+
+fn main() {
+    assert_eq!(CHANNELS, [0, 4, 8, 12]);
+    assert_eq!(unwind(), [3, 2, 1, 0]);
+    assert_eq!(TAPS, [8, 5, 2]);
+}
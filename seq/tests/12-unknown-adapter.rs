@@ -0,0 +1,10 @@
+// Only `.step_by(n)` and `.rev()` are understood after a range, and a step of
+// zero is an error rather than an endless loop.
+
+use seq::seq;
+
+seq!(N in (0..8).skip(2) {});
+
+seq!(N in (0..8).step_by(0) {});
+
+fn main() {}
@@ -0,0 +1,20 @@
+// Several variables separated by commas iterate over every combination of
+// their values, the last one varying fastest as in nested loops. Each can be
+// pasted into identifiers, one after the other.
+
+use seq::seq;
+
+seq!(R in 0..3, C in 0..2 {
+    #(
+        fn cell~R~C() -> (u32, u32) {
+            (R, C)
+        }
+    )*
+
+    const CELLS: [(u32, u32); 6] = [#((R, C),)*];
+});
+
+fn main() {
+    assert_eq!(cell21(), (2, 1));
+    assert_eq!(CELLS, [(0, 0), (0, 1), (1, 0), (1, 1), (2, 0), (2, 1)]);
+}
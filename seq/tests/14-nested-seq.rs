@@ -0,0 +1,52 @@
+// A seq! within the body of another sees the outer variable, in its own range
+// as well as in its body, and keeps its #(...)* sections to itself. Binding
+// the same name again shadows the outer variable. Pastes and expressions using
+// the variables or #index of the inner seq! are left for it to expand.
+
+use seq::seq;
+
+seq!(R in 1..4 {
+    #(
+        fn row~R() -> Vec<u32> {
+            seq!(C in 0..R {
+                vec![#(R * 10 + C,)*]
+            })
+        }
+    )*
+});
+
+seq!(N in 0..2 {
+    fn shadowed~N() -> [u32; 3] {
+        seq!(N in 5..8 {
+            [#(N,)*]
+        })
+    }
+});
+
+seq!(R in 0..2 {
+    seq!(C in 0..3 {
+        #(
+            fn cell~R~C() -> u32 {
+                ~{R * 4 + C}
+            }
+
+            fn transposed~C~R() -> usize {
+                #index
+            }
+
+            fn slot~{R * 3 + #index}() -> (u32, u32) {
+                (R, C)
+            }
+        )*
+    });
+});
+
+fn main() {
+    assert_eq!(row1(), [10]);
+    assert_eq!(row3(), [30, 31, 32]);
+    assert_eq!(shadowed1(), [5, 6, 7]);
+    assert_eq!(cell00(), 0);
+    assert_eq!(cell12(), 6);
+    assert_eq!(transposed21(), 2);
+    assert_eq!(slot4(), (1, 1));
+}
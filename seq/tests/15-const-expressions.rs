@@ -0,0 +1,24 @@
+// A constant expression in braces after a `~` is evaluated while expanding, and
+// stands for its value as an integer literal, or pasted into an identifier as
+// in `reg~{N * 4}`. Expressions take integers and the variables of the seq,
+// with `+ - * / % << >>` at the usual precedence, unary minus and parentheses.
+// A suffix on one of the integers carries over to the result.
+
+use seq::seq;
+
+seq!(N in 0..4 {
+    #(
+        const REG~{N * 4}: u32 = ~{0x4000 + N * 4};
+    )*
+
+    const MASKS: [u8; 4] = [#(~{1u8 << N},)*];
+    const PREVIOUS: [i32; 4] = [#(~{N - 1},)*];
+    const PARITY: [u32; 4] = [#(~{(N + 1) % 2 * -(-3)},)*];
+});
+
+fn main() {
+    assert_eq!(REG12, 0x400c);
+    assert_eq!(MASKS, [1, 2, 4, 8]);
+    assert_eq!(PREVIOUS, [-1, 0, 1, 2]);
+    assert_eq!(PARITY, [3, 0, 3, 0]);
+}
@@ -0,0 +1,25 @@
+// Mistakes in constant expressions are reported within the braces.
+
+use seq::seq;
+
+seq!(N in 0..2 {
+    const A: u32 = ~{N / (N - N)};
+});
+
+seq!(N in 0..2 {
+    const B: u32 = ~{N * M};
+});
+
+seq!(N in 0..2 {
+    const REG~{N - 1}: u32 = 0;
+});
+
+seq!(N in 0..2 {
+    const C: u32 = ~{N 2};
+});
+
+seq!(N in 0..2 {
+    const D: u32 = ~{N + 1.5};
+});
+
+fn main() {}
@@ -0,0 +1,50 @@
+// Besides integer ranges, a variable can run over a list of arbitrary items in
+// brackets, each standing for its tokens, or over a range of characters. In
+// every case `#index` counts the repetitions from zero.
+//
+// Items are split at the commas of the list, but not at those within angle
+// brackets, so `HashMap<u8, u8>` stays one item, and the `>` of `->` is not
+// taken for a closing bracket.
+
+use seq::seq;
+
+trait Width {
+    const BITS: u32;
+    const INDEX: usize;
+}
+
+seq!(T in [u8, u16, u32, Vec<u8>, std::collections::HashMap<u8, Vec<u8>>, fn(u8) -> u8] {
+    #(
+        impl Width for T {
+            const BITS: u32 = (std::mem::size_of::<T>() * 8) as u32;
+            const INDEX: usize = #index;
+        }
+    )*
+});
+
+seq!(C in 'a'..='d' {
+    const LETTERS: [char; 4] = [#(C,)*];
+
+    #(
+        fn field_~C() -> (char, usize) {
+            (C, #index)
+        }
+    )*
+});
+
+seq!(N in [FIRST, SECOND] {
+    #(
+        const N~_SQUARE: usize = ~{#index * #index + 1};
+    )*
+});
+
+fn main() {
+    assert_eq!(<u16 as Width>::BITS, 16);
+    assert_eq!(<u32 as Width>::INDEX, 2);
+    assert_eq!(<Vec<u8> as Width>::INDEX, 3);
+    assert_eq!(<std::collections::HashMap<u8, Vec<u8>> as Width>::INDEX, 4);
+    assert_eq!(<fn(u8) -> u8 as Width>::INDEX, 5);
+    assert_eq!(LETTERS, ['a', 'b', 'c', 'd']);
+    assert_eq!(field_c(), ('c', 2));
+    assert_eq!(SECOND_SQUARE, 2);
+}
@@ -0,0 +1,43 @@
+// The literals a variable becomes keep the suffix and the radix of the range,
+// so `0u8..4u8` gives `0u8`, `1u8`, ... and `0x00..0x10` gives hex literals.
+// Pasted into identifiers, a value is in decimal unless `#[seq(...)]` before
+// the variable asks for another radix and padding with zeros, to match names
+// like `Irq0F` from a datasheet. The value of a pasted expression is written
+// the same way as the variable in it.
+
+use seq::seq;
+
+seq!(N in 0u8..4u8 {
+    fn masks() -> [u8; 4] {
+        [#(N.wrapping_shl(1),)*]
+    }
+
+    const NEXT: [u8; 4] = [#(~{N + 1},)*];
+});
+
+seq!(#[seq(pad = 2, radix = 16)] N in 0x00..0x10 {
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    enum Interrupt {
+        #(
+            Irq~N = N,
+        )*
+    }
+
+    #(
+        const NEXT_OF_IRQ~N: Interrupt = Interrupt::Irq~{(N + 1) % 16};
+    )*
+});
+
+seq!(#[seq(radix = 2, pad = 3)] B in 0..=7u16 {
+    const BITS~B: u16 = B;
+});
+
+fn main() {
+    assert_eq!(masks(), [0, 2, 4, 6]);
+    assert_eq!(NEXT, [1, 2, 3, 4]);
+    assert_eq!(Interrupt::Irq0A as u8, 10);
+    assert_eq!(Interrupt::Irq0F as u8, 15);
+    assert_eq!(BITS101, 5);
+    assert_eq!(NEXT_OF_IRQ09, Interrupt::Irq0A);
+    assert_eq!(NEXT_OF_IRQ0F, Interrupt::Irq00);
+}
@@ -0,0 +1,15 @@
+// `#[seq(...)]` takes a padding and one of the radixes Rust literals can be
+// written in, and only applies to integer ranges. The literals of a range
+// have to agree on their suffix.
+
+use seq::seq;
+
+seq!(#[seq(radix = 12)] N in 0..4 {});
+
+seq!(#[seq(width = 2)] N in 0..4 {});
+
+seq!(#[seq(pad = 2)] C in 'a'..'d' {});
+
+seq!(N in 0u8..4u16 {});
+
+fn main() {}
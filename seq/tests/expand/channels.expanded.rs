@@ -0,0 +1,10 @@
+pub const DMA0_BASE: u32 = 0x4000_0000 + 0 * 0x100;
+pub const DMA4_BASE: u32 = 0x4000_0000 + 4 * 0x100;
+pub const DMA8_BASE: u32 = 0x4000_0000 + 8 * 0x100;
+pub const DMA12_BASE: u32 = 0x4000_0000 + 12 * 0x100;
+
+fn unwind() {
+    release(2);
+    release(1);
+    release(0);
+}
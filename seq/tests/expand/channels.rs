@@ -0,0 +1,15 @@
+use seq::seq;
+
+seq!(N in (0..16).step_by(4) {
+    #(
+        pub const DMA~N~_BASE: u32 = 0x4000_0000 + N * 0x100;
+    )*
+});
+
+seq!(N in (0..3).rev() {
+    fn unwind() {
+        #(
+            release(N);
+        )*
+    }
+});
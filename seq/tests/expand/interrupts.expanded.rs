@@ -0,0 +1,25 @@
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Interrupt {
+    Irq0 = 0,
+    Irq1 = 1,
+    Irq2 = 2,
+    Irq3 = 3,
+}
+impl Interrupt {
+    pub const ALL: [Interrupt; 4] = [
+        Interrupt::Irq0,
+        Interrupt::Irq1,
+        Interrupt::Irq2,
+        Interrupt::Irq3,
+    ];
+}
+
+fn handle1_irq() -> u64 {
+    1 * 2
+}
+fn handle2_irq() -> u64 {
+    2 * 2
+}
+fn handle3_irq() -> u64 {
+    3 * 2
+}
@@ -0,0 +1,20 @@
+use seq::seq;
+
+seq!(N in 0..4 {
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    pub enum Interrupt {
+        #(
+            Irq~N = N,
+        )*
+    }
+
+    impl Interrupt {
+        pub const ALL: [Interrupt; 4] = [#(Interrupt::Irq~N,)*];
+    }
+});
+
+seq!(N in 1..=3 {
+    fn handle~N~_irq() -> u64 {
+        N * 2
+    }
+});
@@ -0,0 +1,6 @@
+pub const REG0: u32 = 1073741824;
+pub const REG0_MASK: u16 = 1u16;
+pub const REG4: u32 = 1073742080;
+pub const REG4_MASK: u16 = 2u16;
+pub const REG8: u32 = 1073742336;
+pub const REG8_MASK: u16 = 4u16;
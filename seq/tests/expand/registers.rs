@@ -0,0 +1,8 @@
+use seq::seq;
+
+seq!(N in 0..3 {
+    #(
+        pub const REG~{N * 4}: u32 = ~{0x4000_0000 + (N << 8)};
+        pub const REG~{N * 4}~_MASK: u16 = ~{1u16 << N};
+    )*
+});
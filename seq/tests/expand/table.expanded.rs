@@ -0,0 +1,13 @@
+pub const CELL00: u8 = 0 * 3 + 0;
+pub const CELL01: u8 = 0 * 3 + 1;
+pub const CELL02: u8 = 0 * 3 + 2;
+pub const CELL10: u8 = 1 * 3 + 0;
+pub const CELL11: u8 = 1 * 3 + 1;
+pub const CELL12: u8 = 1 * 3 + 2;
+
+fn row1() -> [u8; 1] {
+    seq!(C in 0..1 { [# (1 * 10 + C,) *] })
+}
+fn row2() -> [u8; 2] {
+    seq!(C in 0..2 { [# (2 * 10 + C,) *] })
+}
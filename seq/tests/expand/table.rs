@@ -0,0 +1,17 @@
+use seq::seq;
+
+seq!(R in 0..2, C in 0..3 {
+    #(
+        pub const CELL~R~C: u8 = R * 3 + C;
+    )*
+});
+
+seq!(R in 1..3 {
+    #(
+        fn row~R() -> [u8; R] {
+            seq!(C in 0..R {
+                [#(R * 10 + C,)*]
+            })
+        }
+    )*
+});
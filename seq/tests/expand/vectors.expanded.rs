@@ -0,0 +1,6 @@
+pub const IRQ0E: u8 = 0xE;
+pub const IRQ0F: u8 = 0xF;
+pub const IRQ10: u8 = 0x10;
+pub const IRQ11: u8 = 0x11;
+
+const OFFSETS: [u16; 3] = [8u16, 4u16, 0u16];
@@ -0,0 +1,11 @@
+use seq::seq;
+
+seq!(#[seq(pad = 2, radix = 16)] N in 0x0E..0x12 {
+    #(
+        pub const IRQ~N: u8 = N;
+    )*
+});
+
+seq!(N in (0u16..3u16).rev() {
+    const OFFSETS: [u16; 3] = [#(~{N * 4},)*];
+});
@@ -0,0 +1,9 @@
+impl Width for u8 {
+    const INDEX: usize = 0;
+}
+impl Width for i16 {
+    const INDEX: usize = 1;
+}
+impl Width for (HashMap<K, V>) {
+    const INDEX: usize = 2;
+}
@@ -0,0 +1,9 @@
+use seq::seq;
+
+seq!(T in [u8, i16, (HashMap<K, V>)] {
+    #(
+        impl Width for T {
+            const INDEX: usize = #index;
+        }
+    )*
+});
@@ -1,13 +1,23 @@
 #[test]
 fn tests() {
     let t = trybuild::TestCases::new();
-    //t.pass("tests/01-parse-header.rs");
-    //t.pass("tests/02-parse-body.rs");
-    //t.compile_fail("tests/03-expand-four-errors.rs");
-    //t.pass("tests/04-paste-ident.rs");
-    //t.pass("tests/05-repeat-section.rs");
-    //t.pass("tests/06-init-array.rs");
-    //t.pass("tests/07-inclusive-range.rs");
-    //t.compile_fail("tests/08-ident-span.rs");
-    //t.pass("tests/09-interaction-with-macrorules.rs");
+    t.pass("tests/01-parse-header.rs");
+    t.pass("tests/02-parse-body.rs");
+    t.compile_fail("tests/03-expand-four-errors.rs");
+    t.pass("tests/04-paste-ident.rs");
+    t.pass("tests/05-repeat-section.rs");
+    t.pass("tests/06-init-array.rs");
+    t.pass("tests/07-inclusive-range.rs");
+    t.compile_fail("tests/08-ident-span.rs");
+    t.pass("tests/09-interaction-with-macrorules.rs");
+    t.pass("tests/10-paste-suffix-and-sections.rs");
+    t.pass("tests/11-step-and-rev.rs");
+    t.compile_fail("tests/12-unknown-adapter.rs");
+    t.pass("tests/13-multiple-variables.rs");
+    t.pass("tests/14-nested-seq.rs");
+    t.pass("tests/15-const-expressions.rs");
+    t.compile_fail("tests/16-const-expression-errors.rs");
+    t.pass("tests/17-token-lists-and-chars.rs");
+    t.pass("tests/18-suffix-and-radix.rs");
+    t.compile_fail("tests/19-paste-format-errors.rs");
 }
@@ -0,0 +1,316 @@
+// Snapshot tests of macro expansions.
+//
+// The trybuild tests of each project only tell whether the generated code
+// compiles, or which error it produces. This crate records what the generated
+// code actually looks like, so that a change in the expansion shows up in
+// review as a diff of a checked-in file.
+//
+// Procedural macros cannot be invoked outside of the compiler, so the snapshots
+// are taken from a unit test inside the macro crate itself, which hands over
+// the proc_macro2 based function that the macro entry point delegates to:
+//
+//     #[test]
+//     fn expand() {
+//         snapshot::Snapshots::new("tests/expand").derive("Builder", |input| {
+//             expand(syn::parse2(input).unwrap())
+//         });
+//     }
+//
+// Every tests/expand/*.rs file is parsed, each invocation of the macro in it is
+// expanded in-process and the pretty-printed result is compared against the
+// neighbouring *.expanded.rs file. A missing or outdated snapshot fails the
+// test; running the tests with SNAPSHOTS=overwrite writes it out instead.
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use std::fs;
+use std::path::{Path, PathBuf};
+use syn::visit::{self, Visit};
+use syn::{Attribute, Item, Macro, Meta, NestedMeta};
+
+pub struct Snapshots {
+    dir: PathBuf,
+    variant: Option<String>,
+}
+
+impl Snapshots {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Snapshots {
+            dir: dir.as_ref().to_owned(),
+            variant: None,
+        }
+    }
+
+    /// Keeps the snapshots in `*.<variant>.expanded.rs` instead, for
+    /// expansions that depend on the cargo features the macro is built with.
+    pub fn variant(mut self, variant: &str) -> Self {
+        self.variant = Some(variant.to_owned());
+        self
+    }
+
+    /// Expands every item carrying `#[derive(<name>)]`.
+    pub fn derive<F>(&self, name: &str, expand: F)
+    where
+        F: Fn(TokenStream) -> TokenStream,
+    {
+        self.run(name, |file| {
+            let mut visitor = DeriveVisitor {
+                name,
+                inputs: Vec::new(),
+            };
+            visitor.visit_file(file);
+            visitor.inputs.into_iter().map(&expand).collect()
+        });
+    }
+
+    /// Expands every `<name>!(...)` invocation.
+    pub fn function<F>(&self, name: &str, expand: F)
+    where
+        F: Fn(TokenStream) -> TokenStream,
+    {
+        self.run(name, |file| {
+            let mut visitor = FunctionVisitor {
+                name,
+                inputs: Vec::new(),
+            };
+            visitor.visit_file(file);
+            visitor.inputs.into_iter().map(&expand).collect()
+        });
+    }
+
+    /// Expands every item carrying `#[<name>]` or `#[<name>(args)]`.
+    pub fn attribute<F>(&self, name: &str, expand: F)
+    where
+        F: Fn(TokenStream, TokenStream) -> TokenStream,
+    {
+        self.run(name, |file| {
+            let mut visitor = AttributeVisitor {
+                name,
+                inputs: Vec::new(),
+            };
+            visitor.visit_file(file);
+            visitor
+                .inputs
+                .into_iter()
+                .map(|(args, item)| expand(args, item))
+                .collect()
+        });
+    }
+
+    fn run<F>(&self, name: &str, expand: F)
+    where
+        F: Fn(&syn::File) -> Vec<TokenStream>,
+    {
+        let overwrite = std::env::var_os("SNAPSHOTS").is_some_and(|var| var == "overwrite");
+        let mut failures = Vec::new();
+
+        for path in self.inputs() {
+            let source = fs::read_to_string(&path)
+                .unwrap_or_else(|err| panic!("failed to read {}: {}", path.display(), err));
+            let file = syn::parse_file(&source)
+                .unwrap_or_else(|err| panic!("failed to parse {}: {}", path.display(), err));
+
+            let expansions = expand(&file);
+            if expansions.is_empty() {
+                panic!("no invocation of `{}` in {}", name, path.display());
+            }
+            let actual = expansions
+                .into_iter()
+                .map(pretty)
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let snapshot = self.snapshot_path(&path);
+            match fs::read_to_string(&snapshot) {
+                Ok(expected) if expected == actual => {}
+                _ if overwrite => {
+                    eprintln!("NOTE: writing {}", snapshot.display());
+                    fs::write(&snapshot, actual).unwrap_or_else(|err| {
+                        panic!("failed to write {}: {}", snapshot.display(), err)
+                    });
+                }
+                Ok(expected) => {
+                    eprintln!(
+                        "EXPANSION MISMATCH: {}\n\nEXPECTED:\n{}\nACTUAL:\n{}",
+                        snapshot.display(),
+                        expected,
+                        actual,
+                    );
+                    failures.push(snapshot);
+                }
+                Err(_) => {
+                    eprintln!(
+                        "MISSING SNAPSHOT: {}\n\nACTUAL:\n{}",
+                        snapshot.display(),
+                        actual,
+                    );
+                    failures.push(snapshot);
+                }
+            }
+        }
+
+        if !failures.is_empty() {
+            panic!(
+                "{} expansion snapshot(s) missing or out of date, rerun with SNAPSHOTS=overwrite to accept: {:?}",
+                failures.len(),
+                failures,
+            );
+        }
+    }
+
+    fn inputs(&self) -> Vec<PathBuf> {
+        let entries = fs::read_dir(&self.dir)
+            .unwrap_or_else(|err| panic!("failed to read {}: {}", self.dir.display(), err));
+        let mut inputs: Vec<PathBuf> = entries
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| {
+                let file_name = path.file_name().unwrap().to_string_lossy();
+                file_name.ends_with(".rs") && !file_name.ends_with(".expanded.rs")
+            })
+            .collect();
+        inputs.sort();
+        inputs
+    }
+
+    fn snapshot_path(&self, input: &Path) -> PathBuf {
+        let stem = input.file_stem().unwrap().to_string_lossy();
+        let file_name = match &self.variant {
+            Some(variant) => format!("{}.{}.expanded.rs", stem, variant),
+            None => format!("{}.expanded.rs", stem),
+        };
+        input.with_file_name(file_name)
+    }
+}
+
+// Expansions are usually items, but function-like macros in expression
+// position expand to expressions or statements, which are printed as the body
+// of a function instead.
+fn pretty(tokens: TokenStream) -> String {
+    if let Ok(file) = syn::parse2(tokens.clone()) {
+        return prettyplease::unparse(&file);
+    }
+    if let Ok(file) = syn::parse2(quote!(fn expansion() { #tokens })) {
+        return prettyplease::unparse(&file);
+    }
+    format!("{}\n", tokens)
+}
+
+fn is_macro(attr: &Attribute, name: &str) -> bool {
+    attr.path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == name)
+}
+
+struct DeriveVisitor<'a> {
+    name: &'a str,
+    inputs: Vec<TokenStream>,
+}
+
+impl DeriveVisitor<'_> {
+    fn visit_derive_input(&mut self, attrs: &[Attribute], item: &Item) {
+        let derived = attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("derive"))
+            .filter_map(|attr| match attr.parse_meta() {
+                Ok(Meta::List(list)) => Some(list.nested),
+                _ => None,
+            })
+            .flatten()
+            .any(|nested| match nested {
+                NestedMeta::Meta(Meta::Path(path)) => path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == self.name),
+                _ => false,
+            });
+        if derived {
+            // Like the compiler, strip the derive attributes themselves but
+            // keep everything else, including inert helper attributes.
+            let mut item = item.clone();
+            if let Some(attrs) = item_attrs(&mut item) {
+                attrs.retain(|attr| !attr.path.is_ident("derive"));
+            }
+            self.inputs.push(item.into_token_stream());
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for DeriveVisitor<'_> {
+    fn visit_item(&mut self, item: &'ast Item) {
+        match item {
+            Item::Struct(node) => self.visit_derive_input(&node.attrs, item),
+            Item::Enum(node) => self.visit_derive_input(&node.attrs, item),
+            Item::Union(node) => self.visit_derive_input(&node.attrs, item),
+            _ => {}
+        }
+        visit::visit_item(self, item);
+    }
+}
+
+struct FunctionVisitor<'a> {
+    name: &'a str,
+    inputs: Vec<TokenStream>,
+}
+
+impl<'ast> Visit<'ast> for FunctionVisitor<'_> {
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        let matches = mac
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == self.name);
+        if matches {
+            self.inputs.push(mac.tokens.clone());
+        }
+        visit::visit_macro(self, mac);
+    }
+}
+
+struct AttributeVisitor<'a> {
+    name: &'a str,
+    inputs: Vec<(TokenStream, TokenStream)>,
+}
+
+impl<'ast> Visit<'ast> for AttributeVisitor<'_> {
+    fn visit_item(&mut self, item: &'ast Item) {
+        let mut stripped = item.clone();
+        let attrs = match item_attrs(&mut stripped) {
+            Some(attrs) => attrs,
+            None => return visit::visit_item(self, item),
+        };
+        if let Some(index) = attrs.iter().position(|attr| is_macro(attr, self.name)) {
+            let attr = attrs.remove(index);
+            // `#[name(args)]` carries its arguments as one parenthesized group.
+            let args = match attr.tokens.into_iter().next() {
+                Some(proc_macro2::TokenTree::Group(group)) => group.stream(),
+                _ => TokenStream::new(),
+            };
+            self.inputs.push((args, stripped.into_token_stream()));
+        }
+        visit::visit_item(self, item);
+    }
+}
+
+fn item_attrs(item: &mut Item) -> Option<&mut Vec<Attribute>> {
+    let attrs = match item {
+        Item::Const(item) => &mut item.attrs,
+        Item::Enum(item) => &mut item.attrs,
+        Item::ExternCrate(item) => &mut item.attrs,
+        Item::Fn(item) => &mut item.attrs,
+        Item::ForeignMod(item) => &mut item.attrs,
+        Item::Impl(item) => &mut item.attrs,
+        Item::Macro(item) => &mut item.attrs,
+        Item::Macro2(item) => &mut item.attrs,
+        Item::Mod(item) => &mut item.attrs,
+        Item::Static(item) => &mut item.attrs,
+        Item::Struct(item) => &mut item.attrs,
+        Item::Trait(item) => &mut item.attrs,
+        Item::TraitAlias(item) => &mut item.attrs,
+        Item::Type(item) => &mut item.attrs,
+        Item::Union(item) => &mut item.attrs,
+        Item::Use(item) => &mut item.attrs,
+        _ => return None,
+    };
+    Some(attrs)
+}